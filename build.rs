@@ -1,41 +1,142 @@
-//! Read the crates default wordlist and inject it into the binary as an array.
+//! Read the crate's built-in wordlists and inject them into the binary.
+//!
+//! Each wordlist is behind a cargo feature, so that builds only pay for the lists they use.
+//! Each is packed into a single string of words with a table of their lengths, rather than an array of
+//! string literals, as every literal would cost a pointer, a length and a relocation on top of its characters.
+//! The build fails if a list is empty or repeats a word.
 #![allow(clippy::unwrap_used, reason = "build script panics are fine")]
+use std::collections::HashSet;
 use std::env;
 use std::fs;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
 use std::path::Path;
 
 use quote::quote;
 
-// correct as of 5b3d7f8cbfa3b69ae2b917f2b9b53f20f5be1ad6
-const WORDLIST_LEN: usize = 7776;
+/// A wordlist which may be built in.
+struct Wordlist {
+    /// The name given to `--wordlist-name`
+    name: &'static str,
+    /// The cargo feature which enables it, as cargo passes it to build scripts
+    feature: &'static str,
+    /// The file it is read from, with one word per line, optionally after a column of dice rolls
+    path: &'static str,
+    /// Shown in the help message
+    description: &'static str,
+    /// Where to get the file
+    source: &'static str,
+}
+
+/// Every wordlist which may be built in, the default first.
+const WORDLISTS: [Wordlist; 5] = [
+    Wordlist {
+        name: "eff-large",
+        feature: "WORDLIST_EFF_LARGE",
+        path: "wordlists/eff_large_wordlist.txt",
+        description: "EFF large list, 5 dice per word",
+        source: "https://www.eff.org/files/2016/07/18/eff_large_wordlist.txt",
+    },
+    Wordlist {
+        name: "eff-short-1",
+        feature: "WORDLIST_EFF_SHORT_1",
+        path: "wordlists/eff_short_wordlist_1.txt",
+        description: "EFF short list, 4 dice per word, shorter words",
+        source: "https://www.eff.org/files/2016/09/08/eff_short_wordlist_1.txt",
+    },
+    Wordlist {
+        name: "eff-short-2",
+        feature: "WORDLIST_EFF_SHORT_2",
+        path: "wordlists/eff_short_wordlist_2_0.txt",
+        description: "EFF short list, 4 dice per word, unique 3 letter prefixes",
+        source: "https://www.eff.org/files/2016/09/08/eff_short_wordlist_2_0.txt",
+    },
+    Wordlist {
+        name: "pgp-even",
+        feature: "WORDLIST_PGP",
+        path: "wordlists/pgp_even_wordlist.txt",
+        description: "PGP word list for even bytes, 2 syllables",
+        source: "https://en.wikipedia.org/wiki/PGP_word_list",
+    },
+    Wordlist {
+        name: "pgp-odd",
+        feature: "WORDLIST_PGP",
+        path: "wordlists/pgp_odd_wordlist.txt",
+        description: "PGP word list for odd bytes, 3 syllables",
+        source: "https://en.wikipedia.org/wiki/PGP_word_list",
+    },
+];
 
-fn read_lines<T>(filename: T) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    T: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(BufReader::new(file).lines())
+/// Read the words in `wordlist`, dropping the dice rolls of the EFF's own files.
+fn read_words(wordlist: &Wordlist) -> Vec<String> {
+    let Ok(text) = fs::read_to_string(wordlist.path) else {
+        panic!(
+            "the {} wordlist is enabled, but {} could not be read; download it from {}",
+            wordlist.name, wordlist.path, wordlist.source
+        );
+    };
+    text.lines()
+        .filter_map(|line| line.split_whitespace().last())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Reject a wordlist which would quietly weaken every password made from it.
+fn validate(wordlist: &Wordlist, words: &[String]) {
+    assert!(
+        !words.is_empty(),
+        "the {} wordlist in {} has no words",
+        wordlist.name,
+        wordlist.path
+    );
+    let mut seen = HashSet::with_capacity(words.len());
+    for word in words {
+        assert!(
+            seen.insert(word.to_lowercase()),
+            "the {} wordlist in {} has `{word}` more than once",
+            wordlist.name,
+            wordlist.path
+        );
+        assert!(
+            u8::try_from(word.len()).is_ok(),
+            "the {} wordlist in {} has a word longer than 255 bytes",
+            wordlist.name,
+            wordlist.path
+        );
+    }
 }
 
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("wordlist.rs");
 
-    let mut words = Vec::with_capacity(WORDLIST_LEN);
+    let enabled = WORDLISTS
+        .iter()
+        .filter(|wordlist| env::var_os(format!("CARGO_FEATURE_{}", wordlist.feature)).is_some())
+        .collect::<Vec<&Wordlist>>();
+    assert!(
+        !enabled.is_empty(),
+        "at least one wordlist feature must be enabled"
+    );
 
-    if let Ok(lines) = read_lines(Path::new("wordlists/eff_large_wordlist.txt")) {
-        for line in lines.map_while(Result::ok) {
-            words.push(line.to_string());
-        }
-    }
+    let names = enabled.iter().map(|wordlist| wordlist.name);
+    let entries = enabled.iter().map(|wordlist| {
+        let name = wordlist.name;
+        let description = wordlist.description;
+        let words = read_words(wordlist);
+        validate(wordlist, &words);
+        let lengths = words
+            .iter()
+            .map(|word| u8::try_from(word.len()).unwrap())
+            .collect::<Vec<u8>>();
+        let packed = words.concat();
+        quote! { (#name, #description, &[#(#lengths,)*], #packed) }
+    });
 
     let output = quote! {
-        /// Compile-time generated wordlist
-        static WORDLIST: &[&str] = &[#(#words,)*];
+        /// Compile-time generated wordlist names, the default first
+        static WORDLIST_NAMES: &[&str] = &[#(#names,)*];
+        /// Compile-time generated wordlists as name, description, the length of each word in bytes
+        /// and every word one after another, in the order of [`WORDLIST_NAMES`]
+        static WORDLISTS: &[(&str, &str, &[u8], &str)] = &[#(#entries,)*];
     };
 
     fs::write(&dest_path, output.to_string()).unwrap();
@@ -1,6 +1,6 @@
 //! Reduce `ConfigBuilder` boiler plate.
 //!
-//! ```
+//! ```ignore
 //! #[derive(ConfigBuilder, Debug, Default)]
 //! pub struct ConfigBuilder {
 //!     pub field1: Option<String>,
@@ -8,7 +8,7 @@
 //! }
 //! ```
 //! expands to:
-//! ```
+//! ```ignore
 //!impl ConfigBuilder {
 //!    pub fn field1(mut self, value: Option<String>) -> Self {
 //!        self.field1 = value;
@@ -18,10 +18,17 @@
 //!        self.field2 = value;
 //!        self
 //!    }
-//!    pub fn new() -> Self {
-//!        Self::default()
+//!    pub const FIELD_NAMES: &[&str] = &["field1", "field2"];
+//!    pub fn set(&mut self, name: &str, value: Option<String>) -> bool {
+//!        match name {
+//!            "field1" => self.field1 = value,
+//!            "field2" => self.field2 = value,
+//!            _ => return false,
+//!        }
+//!        true
 //!    }
 //!}
+//! ```
 use proc_macro::{self, TokenStream};
 
 use quote::quote;
@@ -57,6 +64,8 @@ pub fn derive(input: TokenStream) -> TokenStream {
         .map(|field| field.ident.clone().unwrap())
         .collect();
 
+    let names: Vec<String> = idents.iter().map(ToString::to_string).collect();
+
     let output = quote! {
         impl #struct_name {
             #(
@@ -65,6 +74,16 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     self
                 }
             )*
+            /// The names of every field, in declaration order.
+            pub const FIELD_NAMES: &[&str] = &[#(#names,)*];
+            /// Set a field by name, returning `false` if there is no such field.
+            pub fn set(&mut self, name: &str, value: Option<String>) -> bool {
+                match name {
+                    #(#names => self.#idents = value,)*
+                    _ => return false,
+                }
+                true
+            }
         }
     };
     output.into()
@@ -0,0 +1,21 @@
+//! Any combination of field values must either build a [`Config`] or fail validation,
+//! and any [`Config`] that builds must be able to make a password or report a policy error.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use fmn_passgen::config::ConfigBuilder;
+use fmn_passgen::password_maker::PasswordMaker;
+
+fuzz_target!(|input: (u64, Vec<Option<String>>)| {
+    let (seed, values) = input;
+    let mut builder = ConfigBuilder::new();
+    for (name, value) in ConfigBuilder::FIELD_NAMES.iter().zip(values) {
+        assert!(builder.set(name, value));
+    }
+    if let Ok(config) = builder.build() {
+        assert!(config.word_min_length <= config.word_max_length);
+        let _ = PasswordMaker::with_rng(SmallRng::seed_from_u64(seed), config).make_password();
+    }
+});
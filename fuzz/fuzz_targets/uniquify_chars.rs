@@ -0,0 +1,18 @@
+//! Uniquified characters are sorted, free of duplicates, and all come from the input.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use fmn_passgen::config::fuzzing::uniquify_chars;
+use fmn_passgen::consts::default::SYMBOL_ALPHABET;
+
+fuzz_target!(|value: Option<String>| {
+    let result = uniquify_chars(value.clone(), &SYMBOL_ALPHABET);
+    match value {
+        None => assert_eq!(result, SYMBOL_ALPHABET),
+        Some(value) => {
+            assert!(result.windows(2).all(|w| w[0] < w[1]));
+            assert!(result.iter().all(|c| value.contains(*c)));
+            assert!(value.chars().all(|c| result.contains(&c)));
+        }
+    }
+});
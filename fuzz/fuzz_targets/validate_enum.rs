@@ -0,0 +1,16 @@
+//! Validated enums round trip through their names, whatever the case of the input.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use fmn_passgen::config::fuzzing::validate_enum;
+use fmn_passgen::types::PaddingType;
+use fmn_passgen::types::StrEnum;
+
+fuzz_target!(|value: Option<String>| {
+    let lowercase = value.as_ref().map(|v| v.to_ascii_lowercase());
+    if let Ok(member) = validate_enum::<PaddingType>(value) {
+        if let Some(lowercase) = lowercase {
+            assert_eq!(member.to_static_str(), lowercase);
+        }
+    }
+});
@@ -0,0 +1,15 @@
+//! Validated integers are always within bounds, and missing values fall back to the default.
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use fmn_passgen::config::fuzzing::validate_int;
+
+fuzz_target!(|input: (Option<String>, u8, u8, u8)| {
+    let (value, min, max, default) = input;
+    let is_none = value.is_none();
+    match validate_int(value, min, max, default) {
+        Ok(result) if is_none => assert_eq!(result, default),
+        Ok(result) => assert!((min..=max).contains(&result)),
+        Err(_) => assert!(!is_none),
+    }
+});
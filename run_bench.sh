@@ -22,7 +22,7 @@ cargo build --profile release --features gui --bin fmn-passgen-gui
 cargo build --profile small --features gui --bin fmn-passgen-gui
 
 TMPFILE="$(basename $0)".md.tmp
-hyperfine --export-markdown $TMPFILE --warmup 3 'target/release/fmn-passgen -c 255 > /dev/null' 'target/small/fmn-passgen -c 255 > /dev/null'
+hyperfine --export-markdown $TMPFILE --warmup 3 'target/release/fmn-passgen -c 255 > /dev/null' 'target/small/fmn-passgen -c 255 > /dev/null' 'target/small/fmn-passgen -c 1 > /dev/null'
 
 echo "# $VERSION Benchmarks - [$SHORTCOMMIT](https://github.com/Raymi306/xkcd-password-gen/tree/$COMMIT)\n" > benchmarks/README.md
 echo "## hyperfine\n" >> benchmarks/README.md
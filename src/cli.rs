@@ -0,0 +1,1336 @@
+//! Describe the command line interface.
+//!
+//! [`commands`] and [`options`] are the single source of truth for what the CLI accepts.
+//! They are used to build the `getopts` parser, the help message, the shell completions
+//! and the man page, so that none of them can drift apart.
+use std::fmt::Write;
+use std::fs;
+
+use getopts::Matches;
+use getopts::Options;
+
+use crate::config::Config;
+use crate::config::ConfigBuilder;
+use crate::config::slot_wordlists_text;
+use crate::consts::default;
+use crate::password_maker::builtin_wordlist_names;
+use crate::password_maker::builtin_wordlists;
+use crate::types::Command;
+use crate::types::ConfigAction;
+use crate::types::DigitClass;
+use crate::types::DigitPlacement;
+use crate::types::JoinedWords;
+use crate::types::KeyboardLayout;
+use crate::types::PaddingFill;
+use crate::types::PaddingType;
+use crate::types::Preset;
+use crate::types::RngType;
+use crate::types::SeparatorType;
+use crate::types::Shell;
+use crate::types::StrEnum;
+use crate::types::WordTransformationType;
+
+/// The name of the CLI binary.
+pub const PROGRAM_NAME: &str = "fmn-passgen";
+
+/// Shown after the option summary in `--help` and in the man page.
+pub const TYPES_HELP: &str = "\
+types are case insensitive
+
+PRESETS:
+    desktop (the usual defaults, for a computer keyboard)
+    mobile  (capitalize-first-word, digits at the ends, mobile keyboard and easy-typing,
+             so that shift is pressed once and separators and padding are on
+             the first symbol layer)
+
+WORD TRANSFORMATIONS:
+    none
+    lower                   (correct horse battery staple)
+    upper                   (CORRECT HORSE BATTERY STAPLE)
+    capitalize-first        (Correct Horse Battery Staple)
+    capitalize-last         (correcT horsE batterY staplE)
+    capitalize-not-first    (cORRECT hORSE bATTERY sTAPLE)
+    capitalize-first-word   (Correct horse battery staple)
+    alternating-lower-upper (correct HORSE battery STAPLE)
+    alternating-upper-lower (CORRECT horse BATTERY staple)
+    random-upper-lower      (correct HORSE battery staple)
+
+DIGIT CLASSES:
+    digits       (0-9)
+    hex          (0-9 and a-f)
+    upper        (A-Z)
+    lower        (a-z)
+    alphanumeric (0-9, A-Z and a-z)
+
+DIGIT PLACEMENTS:
+    ends       (put digits-before before the first word
+                and digits-after after the last)
+    random-gap (put each group of digits in a random gap
+                between, before or after the words)
+    attached   (attach digits-before to the front of a random word,
+                and digits-after to the back of one)
+    every-gap  (put digits-before before the first word,
+                and a group of digits-after after every word)
+
+PADDING TYPES:
+    none
+    fixed-front    (add padding-length padding-characters to front)
+    fixed-back     (add padding-length padding-characters to back)
+    fixed-both     (add padding-length padding-characters to front and back)
+    fixed          (alias for fixed-both)
+    adaptive-front (if length of unpadded password is less than padding-length,
+                    prepend padding-characters to meet length)
+    adaptive-back  (if length of unpadded password is less than padding-length,
+                    append padding-characters to meet length)
+    adaptive       (alias for adaptive-back)
+
+PADDING FILLS:
+    same        (choose one of padding-characters and repeat it at both ends)
+    independent (choose one of padding-front and one of padding-back,
+                 and repeat each at its end)
+    random      (choose every padding character independently
+                 from padding-front and padding-back)
+
+SEPARATOR TYPES:
+    single   (choose one of separators and use it in every gap)
+    per-gap  (choose one of separators for each gap independently)
+    sequence (use separator-sequence in order, starting over when it runs out)
+
+PARTS OF SPEECH:
+    adj  (an adjective, such as brilliant)
+    noun (a noun, such as otter)
+    verb (a verb in the past tense, such as twisted)
+    adv  (an adverb, such as swiftly)
+
+SLOT WORDLISTS:
+    one wordlist for each word in turn, such as \"custom eff-large eff-large\",
+    naming a built-in wordlist, a part of speech, or custom for custom-wordlist
+    name+name mixes wordlists for one word, and name:weight makes each of a
+    wordlist's words weight times as likely, such as custom:50+eff-large
+
+JOINED WORDS:
+    warn        (keep every word, but warn and count the lost entropy)
+    prefix-free (leave out words which start another word, so that passwords can only be read one way)
+
+KEYBOARDS:
+    qwerty (US QWERTY)
+    azerty (French AZERTY)
+    qwertz (German QWERTZ)
+    dvorak (US Dvorak)
+    mobile (a phone's on-screen keyboard, with digits and symbols on separate layers)
+
+RNG TYPES:
+    os-rng (the system's native secure RNG)
+    csprng (a reasonably secure userspace RNG)";
+
+/// [`TYPES_HELP`] followed by the wordlists built into this binary, which depend on its features.
+#[must_use]
+pub fn types_help() -> String {
+    let mut result = format!("{TYPES_HELP}\n\nWORDLISTS:\n");
+    let width = builtin_wordlist_names()
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or_default();
+    for (name, description, size) in builtin_wordlists() {
+        let _ = writeln!(result, "    {name:<width$} ({size} words, {description})");
+    }
+    result.truncate(result.trim_end().len());
+    result
+}
+
+/// What, if anything, an option expects as its argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    /// The option is a flag and takes no argument.
+    None,
+    /// Free form input, such as a number or a list of characters.
+    Free,
+    /// A path to a file.
+    File,
+    /// One of a fixed set of names.
+    Choice(&'static [&'static str]),
+}
+
+/// A subcommand, given as the first free argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliCommand {
+    /// The command
+    pub command: Command,
+    /// What the command does
+    pub description: &'static str,
+    /// The argument which may follow the command
+    pub arg: Arg,
+    /// Whether the argument must be given
+    pub required: bool,
+    /// A word which may come before the argument, such as `check` in `wordlist check FILE`
+    pub action: Option<&'static str>,
+}
+
+impl CliCommand {
+    /// The name of the command.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.command.to_static_str()
+    }
+    /// Describe how the command is used, such as `config [options] [print|check|export]`.
+    #[must_use]
+    pub fn synopsis(&self) -> String {
+        let arg = match self.arg {
+            Arg::None => return format!("{} [options]", self.name()),
+            Arg::Free => "ARG".to_owned(),
+            Arg::File => "FILE".to_owned(),
+            Arg::Choice(choices) => choices.join("|"),
+        };
+        let action = self
+            .action
+            .map_or_else(String::new, |action| format!("[{action}] "));
+        if self.required {
+            format!("{} [options] {action}{arg}", self.name())
+        } else {
+            format!("{} [options] {action}[{arg}]", self.name())
+        }
+    }
+    /// Check whether `arg` is an acceptable (number of) argument(s).
+    #[must_use]
+    pub const fn accepts(&self, arg: Option<&String>) -> bool {
+        match (self.arg, arg) {
+            (Arg::None, Some(_)) => false,
+            (_, None) => !self.required,
+            _ => true,
+        }
+    }
+    /// Split `args`, the free arguments after the command, into whether the action was given and the argument.
+    ///
+    /// Returns `None` if they are not acceptable.
+    #[must_use]
+    pub fn arguments<'a>(&self, args: &'a [String]) -> Option<(bool, Option<&'a String>)> {
+        let (action, args) = match (self.action, args) {
+            (Some(action), [first, rest @ ..]) if first == action => (true, rest),
+            _ => (false, args),
+        };
+        match args {
+            [] | [_] if self.accepts(args.first()) => Some((action, args.first())),
+            _ => None,
+        }
+    }
+}
+
+/// All of the subcommands, in the order [`Command`] declares them, so the default comes first.
+#[must_use]
+pub const fn commands() -> [CliCommand; 6] {
+    [
+        CliCommand {
+            command: Command::Generate,
+            description: "make passwords, the default",
+            arg: Arg::None,
+            required: false,
+            action: None,
+        },
+        CliCommand {
+            command: Command::Entropy,
+            description: "estimate the strength of passwords",
+            arg: Arg::None,
+            required: false,
+            action: None,
+        },
+        CliCommand {
+            command: Command::Typing,
+            description: "make passwords and show how costly each is to type on the keyboard",
+            arg: Arg::None,
+            required: false,
+            action: None,
+        },
+        CliCommand {
+            command: Command::Wordlist,
+            description: "show wordlist statistics, or check the wordlist or the one in FILE",
+            arg: Arg::File,
+            required: false,
+            action: Some("check"),
+        },
+        CliCommand {
+            command: Command::Config,
+            description: "print, check or export the effective config",
+            arg: Arg::Choice(ConfigAction::NAMES),
+            required: false,
+            action: None,
+        },
+        CliCommand {
+            command: Command::Completions,
+            description: "print a shell completion script",
+            arg: Arg::Choice(Shell::NAMES),
+            required: true,
+            action: None,
+        },
+    ]
+}
+
+/// Look up the [`CliCommand`] for `command`.
+#[must_use]
+pub const fn command(command: Command) -> CliCommand {
+    // the table is in declaration order
+    commands()[command as usize]
+}
+
+/// A single command line option.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliOption {
+    /// The short name, without the leading `-`, may be empty
+    pub short: &'static str,
+    /// The long name, without the leading `--`
+    pub long: &'static str,
+    /// What the option does
+    pub description: &'static str,
+    /// Describes the expected argument and its default
+    pub hint: String,
+    /// The expected argument
+    pub arg: Arg,
+}
+
+impl CliOption {
+    const fn flag(short: &'static str, long: &'static str, description: &'static str) -> Self {
+        Self {
+            short,
+            long,
+            description,
+            hint: String::new(),
+            arg: Arg::None,
+        }
+    }
+    const fn free(
+        short: &'static str,
+        long: &'static str,
+        description: &'static str,
+        hint: String,
+    ) -> Self {
+        Self {
+            short,
+            long,
+            description,
+            hint,
+            arg: Arg::Free,
+        }
+    }
+    const fn file(
+        short: &'static str,
+        long: &'static str,
+        description: &'static str,
+        hint: String,
+    ) -> Self {
+        Self {
+            short,
+            long,
+            description,
+            hint,
+            arg: Arg::File,
+        }
+    }
+    fn choice<T: StrEnum>(
+        short: &'static str,
+        long: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            short,
+            long,
+            description,
+            hint: format!("TYPE, default={}", T::default().to_static_str()),
+            arg: Arg::Choice(T::NAMES),
+        }
+    }
+}
+
+/// All of the options accepted by the CLI, in the order they are displayed.
+///
+/// They are shared by every subcommand.
+#[must_use]
+#[expect(clippy::too_many_lines, reason = "it is a flat table of options")]
+pub fn options() -> Vec<CliOption> {
+    // TODO this ought to be const
+    let default_symbol_alphabet_help: String = format!(
+        "CHOICES, default=\"{}\"",
+        default::SYMBOL_ALPHABET.into_iter().collect::<String>()
+    );
+    vec![
+        CliOption::flag("h", "help", "print this help message"),
+        CliOption::free(
+            "c",
+            "count",
+            "how many passwords to make",
+            format!("NUM, default={}", default::COUNT),
+        ),
+        CliOption::choice::<Preset>(
+            "",
+            "preset",
+            "defaults for where the passwords will be typed, the other options still win",
+        ),
+        CliOption {
+            short: "",
+            long: "wordlist-name",
+            description: "built-in wordlist to choose words from",
+            hint: format!("NAME, default={}", builtin_wordlist_names()[0]),
+            arg: Arg::Choice(builtin_wordlist_names()),
+        },
+        CliOption::file(
+            "",
+            "custom-wordlist",
+            "read a custom wordlist from FILE, one word per line, for slot-wordlists",
+            "FILE, default=none".to_owned(),
+        ),
+        CliOption::free(
+            "w",
+            "word-count",
+            "number of words",
+            format!("NUM, default={}", default::WORD_COUNT),
+        ),
+        CliOption::free(
+            "",
+            "template",
+            "parts of speech to fill in order, such as \"adj noun verb adv\", instead of word-count words",
+            "PARTS, default=none".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "slot-wordlists",
+            "wordlists to draw each word from in turn, such as \"custom eff-large eff-large\", instead of word-count words",
+            "SLOTS, default=none".to_owned(),
+        ),
+        CliOption::free(
+            "m",
+            "word-min-length",
+            "minimum length of a chosen word",
+            format!("NUM, default={}", default::WORD_MIN_LENGTH),
+        ),
+        CliOption::free(
+            "M",
+            "word-max-length",
+            "maximum length of a chosen word",
+            format!("NUM, default={}", default::WORD_MAX_LENGTH),
+        ),
+        CliOption::choice::<WordTransformationType>(
+            "W",
+            "word-transformation",
+            "transformation to apply to the selected words",
+        ),
+        CliOption::free(
+            "b",
+            "digits-before",
+            "number of digits to prepend",
+            format!("NUM, default={}", default::DIGITS_BEFORE),
+        ),
+        CliOption::free(
+            "a",
+            "digits-after",
+            "number of digits to append",
+            format!("NUM, default={}", default::DIGITS_AFTER),
+        ),
+        CliOption::choice::<DigitPlacement>("D", "digit-placement", "where to put the digits"),
+        CliOption::choice::<DigitClass>(
+            "C",
+            "digit-class",
+            "character class to choose digits from",
+        ),
+        CliOption::free(
+            "",
+            "digit-characters",
+            "list of characters to choose digits from instead",
+            "CHARS, default=the digit class".to_owned(),
+        ),
+        CliOption::choice::<PaddingType>("T", "padding-type", "how to apply padding"),
+        CliOption::free(
+            "l",
+            "padding-length",
+            "how much to pad",
+            format!(
+                "NUM, default={} for fixed, {} for adaptive",
+                default::PADDING_LENGTH_FIXED,
+                default::PADDING_LENGTH_ADAPTIVE
+            ),
+        ),
+        CliOption::free(
+            "p",
+            "padding-characters",
+            "list of characters to choose from",
+            default_symbol_alphabet_help.clone(),
+        ),
+        CliOption::choice::<PaddingFill>("F", "padding-fill", "how to choose padding characters"),
+        CliOption::free(
+            "",
+            "padding-front",
+            "list of characters to choose front padding from, unless the fill is same",
+            "CHARS, default=padding-characters".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "padding-back",
+            "list of characters to choose back padding from, unless the fill is same",
+            "CHARS, default=padding-characters".to_owned(),
+        ),
+        CliOption::free(
+            "s",
+            "separators",
+            "list of characters to choose from",
+            default_symbol_alphabet_help,
+        ),
+        CliOption::choice::<SeparatorType>("S", "separator-type", "how to choose separators"),
+        CliOption::free(
+            "",
+            "separator-sequence",
+            "separators to use in order, for the sequence separator type",
+            "CHARS, default=none".to_owned(),
+        ),
+        CliOption::choice::<JoinedWords>(
+            "",
+            "joined-words",
+            "what to do when words run together and read as others",
+        ),
+        CliOption::flag(
+            "",
+            "avoid-ambiguous",
+            "leave out look-alike characters (0 O 1 l I | `) and words that would contain them",
+        ),
+        CliOption::choice::<KeyboardLayout>("", "keyboard", "the keyboard passwords are typed on"),
+        CliOption::flag(
+            "",
+            "easy-typing",
+            "leave out separators, padding and words needing shift or AltGr on the keyboard, or on mobile a symbol layer beyond the first",
+        ),
+        CliOption::flag(
+            "",
+            "blocklist",
+            "leave out words some would rather not see in a password, even across two words",
+        ),
+        CliOption::file(
+            "",
+            "exclude-words",
+            "leave out the words in FILE, one per line",
+            "FILE, default=none".to_owned(),
+        ),
+        CliOption::choice::<RngType>("r", "rng", "method of random number generation"),
+        CliOption::free(
+            "",
+            "min-upper",
+            "minimum number of uppercase characters",
+            "NUM, default=0".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "min-lower",
+            "minimum number of lowercase characters",
+            "NUM, default=0".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "min-digits",
+            "minimum number of digits",
+            "NUM, default=0".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "min-symbols",
+            "minimum number of symbols",
+            "NUM, default=0".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "max-length",
+            "maximum length of a password",
+            "NUM, default=0 for no limit".to_owned(),
+        ),
+        CliOption::free(
+            "",
+            "max-repeat",
+            "maximum number of identical consecutive characters",
+            "NUM, default=0 for no limit".to_owned(),
+        ),
+        CliOption::flag(
+            "",
+            "explain",
+            "show where the strength of the passwords comes from instead of making them",
+        ),
+        CliOption::flag("", "man", "print a man page"),
+    ]
+}
+
+/// Build the `getopts` parser from [`options`].
+#[must_use]
+pub fn getopts_options() -> Options {
+    let mut opts = Options::new();
+    for option in options() {
+        match option.arg {
+            Arg::None => opts.optflag(option.short, option.long, option.description),
+            Arg::Free | Arg::File | Arg::Choice(_) => {
+                opts.optopt(option.short, option.long, option.description, &option.hint)
+            }
+        };
+    }
+    opts
+}
+
+/// The help message's introduction, to be passed to [`Options::usage`].
+#[must_use]
+pub fn brief(program_name: &str) -> String {
+    let mut result = String::new();
+    for (i, command) in commands().iter().enumerate() {
+        let synopsis = if i == 0 {
+            format!("[{}] [options]", command.name())
+        } else {
+            command.synopsis()
+        };
+        let prefix = if i == 0 { "Usage:" } else { "" };
+        let _ = writeln!(result, "{prefix:<6} {program_name} {synopsis}");
+    }
+    result.push_str("\nCommands:\n");
+    for command in commands() {
+        let _ = writeln!(result, "    {:<20}{}", command.name(), command.description);
+    }
+    result.truncate(result.trim_end().len());
+    result
+}
+
+/// Fill a [`ConfigBuilder`] with the options in `matches`.
+///
+/// # Errors
+///
+/// Will return a message naming the file if the `--exclude-words` or `--custom-wordlist` file can not be read.
+pub fn config_builder(matches: &Matches) -> Result<ConfigBuilder, String> {
+    let read = |name| {
+        matches
+            .opt_str(name)
+            .map(|path| fs::read_to_string(&path).map_err(|e| format!("{path}: {e}")))
+            .transpose()
+    };
+    let excluded_words = read("exclude-words")?;
+    let custom_wordlist = read("custom-wordlist")?;
+    Ok(ConfigBuilder::new()
+        .count(matches.opt_str("count"))
+        .preset(matches.opt_str("preset"))
+        .wordlist_name(matches.opt_str("wordlist-name"))
+        .custom_wordlist(custom_wordlist)
+        .word_count(matches.opt_str("word-count"))
+        .template(matches.opt_str("template"))
+        .slot_wordlists(matches.opt_str("slot-wordlists"))
+        .word_min_length(matches.opt_str("word-min-length"))
+        .word_max_length(matches.opt_str("word-max-length"))
+        .word_transformation(matches.opt_str("word-transformation"))
+        .digits_before(matches.opt_str("digits-before"))
+        .digits_after(matches.opt_str("digits-after"))
+        .digit_placement(matches.opt_str("digit-placement"))
+        .digit_class(matches.opt_str("digit-class"))
+        .digit_characters(matches.opt_str("digit-characters"))
+        .padding_type(matches.opt_str("padding-type"))
+        .padding_length(matches.opt_str("padding-length"))
+        .padding_characters(matches.opt_str("padding-characters"))
+        .padding_fill(matches.opt_str("padding-fill"))
+        .padding_front_characters(matches.opt_str("padding-front"))
+        .padding_back_characters(matches.opt_str("padding-back"))
+        .separator_characters(matches.opt_str("separators"))
+        .separator_type(matches.opt_str("separator-type"))
+        .separator_sequence(matches.opt_str("separator-sequence"))
+        .joined_words(matches.opt_str("joined-words"))
+        .avoid_ambiguous(
+            matches
+                .opt_present("avoid-ambiguous")
+                .then(|| "true".to_owned()),
+        )
+        .keyboard(matches.opt_str("keyboard"))
+        .easy_typing(
+            matches
+                .opt_present("easy-typing")
+                .then(|| "true".to_owned()),
+        )
+        .blocklist(matches.opt_present("blocklist").then(|| "true".to_owned()))
+        .excluded_words(excluded_words)
+        .rng_type(matches.opt_str("rng"))
+        .min_uppercase(matches.opt_str("min-upper"))
+        .min_lowercase(matches.opt_str("min-lower"))
+        .min_digits(matches.opt_str("min-digits"))
+        .min_symbols(matches.opt_str("min-symbols"))
+        .max_length(matches.opt_str("max-length"))
+        .max_consecutive(matches.opt_str("max-repeat")))
+}
+
+/// Quote `text` for POSIX shells.
+fn shell_quote(text: &str) -> String {
+    if !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,/:=+@%".contains(c))
+    {
+        return text.to_owned();
+    }
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// The options which reproduce `config`, as the inverse of [`config_builder`].
+///
+/// Excluded words and the custom wordlist are left out, as only the files they were read from could give them back.
+#[must_use]
+pub fn config_args(config: &Config) -> Vec<(&'static str, String)> {
+    vec![
+        ("count", config.count.to_string()),
+        ("preset", config.preset.to_static_str().to_owned()),
+        ("wordlist-name", config.wordlist_name.to_owned()),
+        ("word-count", config.word_count.to_string()),
+        (
+            "template",
+            config
+                .template
+                .iter()
+                .map(StrEnum::to_static_str)
+                .collect::<Vec<&str>>()
+                .join(" "),
+        ),
+        (
+            "slot-wordlists",
+            slot_wordlists_text(&config.slot_wordlists),
+        ),
+        ("word-min-length", config.word_min_length.to_string()),
+        ("word-max-length", config.word_max_length.to_string()),
+        (
+            "word-transformation",
+            config.word_transformation.to_static_str().to_owned(),
+        ),
+        ("digits-before", config.digits_before.to_string()),
+        ("digits-after", config.digits_after.to_string()),
+        (
+            "digit-placement",
+            config.digit_placement.to_static_str().to_owned(),
+        ),
+        ("digit-class", config.digit_class.to_static_str().to_owned()),
+        ("digit-characters", config.digit_characters.iter().collect()),
+        (
+            "padding-type",
+            config.padding_type.to_static_str().to_owned(),
+        ),
+        ("padding-length", config.padding_length.to_string()),
+        (
+            "padding-characters",
+            config.padding_characters.iter().collect(),
+        ),
+        (
+            "padding-fill",
+            config.padding_fill.to_static_str().to_owned(),
+        ),
+        (
+            "padding-front",
+            config.padding_front_characters.iter().collect(),
+        ),
+        (
+            "padding-back",
+            config.padding_back_characters.iter().collect(),
+        ),
+        ("separators", config.separator_characters.iter().collect()),
+        (
+            "separator-type",
+            config.separator_type.to_static_str().to_owned(),
+        ),
+        (
+            "separator-sequence",
+            config.separator_sequence.iter().collect(),
+        ),
+        (
+            "joined-words",
+            config.joined_words.to_static_str().to_owned(),
+        ),
+        ("avoid-ambiguous", config.avoid_ambiguous.to_string()),
+        ("keyboard", config.keyboard.to_static_str().to_owned()),
+        ("easy-typing", config.easy_typing.to_string()),
+        ("blocklist", config.blocklist.to_string()),
+        ("rng", config.rng_type.to_static_str().to_owned()),
+        ("min-upper", config.policy.min_uppercase.to_string()),
+        ("min-lower", config.policy.min_lowercase.to_string()),
+        ("min-digits", config.policy.min_digits.to_string()),
+        ("min-symbols", config.policy.min_symbols.to_string()),
+        ("max-length", config.policy.max_length.to_string()),
+        ("max-repeat", config.policy.max_consecutive.to_string()),
+    ]
+}
+
+/// A shell command line which makes passwords with `config`.
+///
+/// Options which `config` would get anyway are left out,
+/// which also keeps the default character lists in their own order rather than the sorted one options give.
+///
+/// # Errors
+///
+/// Will return a message if `config` has a custom wordlist or excluded words,
+/// as only the files they were read from could give them back.
+pub fn export(config: &Config) -> Result<String, String> {
+    if !config.custom_wordlist.is_empty() || !config.excluded_words.is_empty() {
+        return Err(
+            "a config with --custom-wordlist or --exclude-words can not be exported, as the files they were read from are not kept"
+                .to_owned(),
+        );
+    }
+    let flags = options()
+        .into_iter()
+        .filter(|option| option.arg == Arg::None)
+        .map(|option| option.long)
+        .collect::<Vec<&str>>();
+    let arg = |long: &str, value: &str| {
+        if flags.contains(&long) {
+            (value == "true").then(|| format!("--{long}"))
+        } else {
+            Some(format!("--{long}={value}"))
+        }
+    };
+    let rebuild = |args: &[(&str, String)]| {
+        getopts_options()
+            .parse(args.iter().filter_map(|(long, value)| arg(long, value)))
+            .ok()
+            .and_then(|matches| config_builder(&matches).ok())
+            .and_then(|builder| builder.build().ok())
+    };
+    // add the first option the config does not get yet until it gets them all,
+    // as earlier options such as the preset change the defaults of later ones,
+    // along with any options after it which it can not be built without
+    let all = config_args(config);
+    let mut args: Vec<(&str, String)> = Vec::new();
+    let mut built = rebuild(&args);
+    while let Some(config) = &built {
+        let got = config_args(config);
+        let missing = all
+            .iter()
+            .zip(&got)
+            .filter(|(wanted, got)| wanted != got && !args.contains(wanted))
+            .map(|(wanted, _)| wanted.clone())
+            .collect::<Vec<(&str, String)>>();
+        if missing.is_empty() {
+            if all != got {
+                args.clone_from(&all);
+            }
+            break;
+        }
+        built = None;
+        for option in missing {
+            args.push(option);
+            built = rebuild(&args);
+            if built.is_some() {
+                break;
+            }
+        }
+    }
+    if built.is_none() {
+        args.clone_from(&all);
+    }
+    // then drop the options which later ones made redundant
+    let mut i = 0;
+    while i < args.len() {
+        let option = args.remove(i);
+        if rebuild(&args).as_ref() != Some(config) {
+            args.insert(i, option);
+            i += 1;
+        }
+    }
+    args.sort_by_key(|(long, _)| all.iter().position(|(l, _)| l == long));
+    let mut result = format!("{PROGRAM_NAME} {}", Command::Generate.to_static_str());
+    for (long, value) in args {
+        if let Some(arg) = arg(long, &shell_quote(&value)) {
+            let _ = write!(result, " {arg}");
+        }
+    }
+    Ok(result)
+}
+
+/// Generate a completion script for `shell`.
+#[must_use]
+pub fn completions(shell: Shell) -> String {
+    let options = options();
+    match shell {
+        Shell::Bash => bash_completions(&options),
+        Shell::Zsh => zsh_completions(&options),
+        Shell::Fish => fish_completions(&options),
+    }
+}
+
+/// `-c` and `--count` style names for an option.
+fn dashed_names(option: &CliOption) -> Vec<String> {
+    let mut names = Vec::with_capacity(2);
+    if !option.short.is_empty() {
+        names.push(format!("-{}", option.short));
+    }
+    names.push(format!("--{}", option.long));
+    names
+}
+
+/// The space separated names of every command.
+fn command_names() -> String {
+    commands()
+        .iter()
+        .map(CliCommand::name)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn bash_completions(options: &[CliOption]) -> String {
+    let function = format!("_{}", PROGRAM_NAME.replace('-', "_"));
+    let reply = |words: &str| format!("COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\"))");
+    let file_reply = "COMPREPLY=($(compgen -f -- \"$cur\"))";
+    let mut option_cases = String::new();
+    let mut takes_value = Vec::new();
+    for option in options {
+        let action = match option.arg {
+            Arg::None => continue,
+            Arg::Free => "return 0".to_owned(),
+            Arg::File => format!("{file_reply}; return 0"),
+            Arg::Choice(choices) => format!("{}; return 0", reply(&choices.join(" "))),
+        };
+        let names = dashed_names(option).join("|");
+        let _ = writeln!(option_cases, "        {names}) {action} ;;");
+        takes_value.push(names);
+    }
+    let mut command_cases = String::new();
+    for command in commands() {
+        let action = match (command.arg, command.action) {
+            (Arg::None, _) => continue,
+            (Arg::Free, _) => "return 0".to_owned(),
+            (Arg::File, None) => format!("{file_reply}; return 0"),
+            (Arg::File, Some(action)) => {
+                format!("COMPREPLY=($(compgen -W \"{action}\" -f -- \"$cur\")); return 0")
+            }
+            (Arg::Choice(choices), _) => format!("{}; return 0", reply(&choices.join(" "))),
+        };
+        let _ = writeln!(command_cases, "            {}) {action} ;;", command.name());
+    }
+    let all_options = options
+        .iter()
+        .flat_map(dashed_names)
+        .collect::<Vec<String>>()
+        .join(" ");
+    format!(
+        r#"# bash completion for {PROGRAM_NAME}
+{function}() {{
+    local cur prev command i
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    case "$prev" in
+{option_cases}    esac
+    # find the command, skipping over options and their values
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${{COMP_WORDS[i]}}" in
+            {takes_value}) ((i++)) ;;
+            -*) ;;
+            *) command="${{COMP_WORDS[i]}}"; break ;;
+        esac
+    done
+    if [[ "$cur" != -* ]]; then
+        case "$command" in
+            "") {command_reply}; return 0 ;;
+{command_cases}        esac
+    fi
+    {option_reply}
+}}
+complete -F {function} {PROGRAM_NAME}
+"#,
+        takes_value = takes_value.join("|"),
+        command_reply = reply(&command_names()),
+        option_reply = reply(&all_options),
+    )
+}
+
+/// Escape text for use inside a single quoted zsh `_arguments` spec.
+fn zsh_escape(text: &str) -> String {
+    text.replace('\'', r"'\''")
+        .replace('[', r"\[")
+        .replace(']', r"\]")
+        .replace(':', r"\:")
+}
+
+fn zsh_completions(options: &[CliOption]) -> String {
+    let function = format!("_{}", PROGRAM_NAME.replace('-', "_"));
+    let mut specs = String::new();
+    for option in options {
+        let description = zsh_escape(option.description);
+        let action = match option.arg {
+            Arg::None => String::new(),
+            Arg::Free => format!(":{}: ", zsh_escape(&option.hint)),
+            Arg::File => format!(":{}:_files", zsh_escape(&option.hint)),
+            Arg::Choice(choices) => format!(":TYPE:({})", choices.join(" ")),
+        };
+        let (short_suffix, long_suffix) = if option.arg == Arg::None {
+            ("", "")
+        } else {
+            ("+", "=")
+        };
+        let _ = if option.short.is_empty() {
+            writeln!(
+                specs,
+                "        '--{}{long_suffix}[{description}]{action}' \\",
+                option.long
+            )
+        } else {
+            writeln!(
+                specs,
+                "        '(-{short} --{long})'{{-{short}{short_suffix},--{long}{long_suffix}}}'[{description}]{action}' \\",
+                short = option.short,
+                long = option.long,
+            )
+        };
+    }
+    let mut command_cases = String::new();
+    for command in commands() {
+        let action = match (command.arg, command.action) {
+            (Arg::None | Arg::Free, _) => continue,
+            (Arg::File, None) => "_files".to_owned(),
+            (Arg::File, Some(action)) => format!("compadd -- {action}; _files"),
+            (Arg::Choice(choices), _) => format!("compadd -- {}", choices.join(" ")),
+        };
+        let _ = writeln!(
+            command_cases,
+            "                {}) {action} ;;",
+            command.name()
+        );
+    }
+    format!(
+        "#compdef {PROGRAM_NAME}
+
+{function}() {{
+    local state line
+    _arguments -s -C \\
+{specs}        '1:command:({commands})' \\
+        '2:argument:->argument'
+    case $state in
+        argument)
+            case $line[1] in
+{command_cases}            esac
+            ;;
+    esac
+}}
+
+{function} \"$@\"
+",
+        commands = command_names(),
+    )
+}
+
+/// Escape text for use inside a single quoted fish string.
+fn fish_escape(text: &str) -> String {
+    text.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+fn fish_completions(options: &[CliOption]) -> String {
+    let mut result =
+        format!("# fish completion for {PROGRAM_NAME}\ncomplete -c {PROGRAM_NAME} -f\n");
+    for command in commands() {
+        let _ = writeln!(
+            result,
+            "complete -c {PROGRAM_NAME} -n '__fish_use_subcommand' -a '{}' -d '{}'",
+            command.name(),
+            fish_escape(command.description)
+        );
+        let condition = format!("-n '__fish_seen_subcommand_from {}'", command.name());
+        if let Some(action) = command.action {
+            let _ = writeln!(
+                result,
+                "complete -c {PROGRAM_NAME} {condition} -a '{action}'"
+            );
+        }
+        let _ = match command.arg {
+            Arg::None | Arg::Free => continue,
+            Arg::File => writeln!(result, "complete -c {PROGRAM_NAME} {condition} -F"),
+            Arg::Choice(choices) => writeln!(
+                result,
+                "complete -c {PROGRAM_NAME} {condition} -a '{}'",
+                choices.join(" ")
+            ),
+        };
+    }
+    for option in options {
+        result.push_str("complete -c ");
+        result.push_str(PROGRAM_NAME);
+        if !option.short.is_empty() {
+            let _ = write!(result, " -s {}", option.short);
+        }
+        let _ = write!(
+            result,
+            " -l {} -d '{}'",
+            option.long,
+            fish_escape(option.description)
+        );
+        match option.arg {
+            Arg::None => {}
+            Arg::Free => result.push_str(" -x"),
+            Arg::File => result.push_str(" -r -F"),
+            Arg::Choice(choices) => {
+                let _ = write!(result, " -x -a '{}'", choices.join(" "));
+            }
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Escape text for roff, so that it is not interpreted as a request or escape sequence.
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', r"\e").replace('-', r"\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!(r"\&{escaped}")
+    } else {
+        escaped
+    }
+}
+
+/// Generate a man page in roff format.
+#[must_use]
+pub fn man_page() -> String {
+    let mut result = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"
+.SH NAME
+{} \\- {}
+.SH SYNOPSIS
+",
+        roff_escape(&PROGRAM_NAME.to_ascii_uppercase()),
+        roff_escape(PROGRAM_NAME),
+        env!("CARGO_PKG_VERSION"),
+        roff_escape(PROGRAM_NAME),
+        roff_escape(&env!("CARGO_PKG_DESCRIPTION").to_ascii_lowercase()),
+    );
+    for (i, command) in commands().iter().enumerate() {
+        if i > 0 {
+            result.push_str(".br\n");
+        }
+        let synopsis = if i == 0 {
+            format!("[{}] [options]", command.name())
+        } else {
+            command.synopsis()
+        };
+        let _ = writeln!(
+            result,
+            ".B {}\n{}",
+            roff_escape(PROGRAM_NAME),
+            roff_escape(&synopsis)
+        );
+    }
+    result.push_str(
+        ".SH DESCRIPTION
+Generate memorable passwords made of random words, digits, separators and padding.
+.SH COMMANDS
+",
+    );
+    for command in commands() {
+        let _ = writeln!(
+            result,
+            ".TP\n\\fB{}\\fR\n{}",
+            roff_escape(command.name()),
+            roff_escape(command.description)
+        );
+    }
+    result.push_str(".SH OPTIONS\n");
+    for option in options() {
+        result.push_str(".TP\n");
+        let names = dashed_names(&option)
+            .iter()
+            .map(|name| format!(r"\fB{}\fR", roff_escape(name)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        result.push_str(&names);
+        if !option.hint.is_empty() {
+            let _ = write!(result, r" \fI{}\fR", roff_escape(&option.hint));
+        }
+        result.push('\n');
+        result.push_str(&roff_escape(option.description));
+        result.push('\n');
+    }
+    result.push_str(".SH TYPES\n.nf\n");
+    for line in types_help().lines() {
+        result.push_str(&roff_escape(line));
+        result.push('\n');
+    }
+    result.push_str(".fi\n");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choices() -> Vec<&'static str> {
+        options()
+            .iter()
+            .map(|o| o.arg)
+            .chain(commands().iter().map(|c| c.arg))
+            .filter_map(|arg| match arg {
+                Arg::Choice(choices) => Some(choices),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_options_unique() {
+        let options = options();
+        for (i, option) in options.iter().enumerate() {
+            assert!(
+                options[i + 1..]
+                    .iter()
+                    .all(|o| o.long != option.long
+                        && (o.short.is_empty() || o.short != option.short))
+            );
+        }
+    }
+
+    #[test]
+    fn test_commands_complete() {
+        assert_eq!(commands()[0].command, Command::default());
+        for (name, command) in Command::NAME_MEMBER_ARR {
+            assert_eq!(super::command(*command).command, *command);
+            assert_eq!(super::command(*command).name(), *name);
+        }
+    }
+
+    #[test]
+    fn test_command_accepts() {
+        let arg = "x".to_owned();
+        assert!(command(Command::Generate).accepts(None));
+        assert!(!command(Command::Generate).accepts(Some(&arg)));
+        assert!(command(Command::Wordlist).accepts(None));
+        assert!(command(Command::Wordlist).accepts(Some(&arg)));
+        assert!(!command(Command::Completions).accepts(None));
+        assert!(command(Command::Completions).accepts(Some(&arg)));
+    }
+
+    #[test]
+    fn test_command_arguments() {
+        let args = |args: &[&str]| args.iter().map(|&arg| arg.to_owned()).collect::<Vec<_>>();
+        let file = "words.txt".to_owned();
+        let wordlist = command(Command::Wordlist);
+        assert_eq!(wordlist.arguments(&[]), Some((false, None)));
+        assert_eq!(wordlist.arguments(&args(&["check"])), Some((true, None)));
+        assert_eq!(
+            wordlist.arguments(&args(&["words.txt"])),
+            Some((false, Some(&file)))
+        );
+        assert_eq!(
+            wordlist.arguments(&args(&["check", "words.txt"])),
+            Some((true, Some(&file)))
+        );
+        assert_eq!(wordlist.arguments(&args(&["words.txt", "check"])), None);
+        assert_eq!(
+            command(Command::Config).arguments(&args(&["check"])),
+            Some((false, Some(&"check".to_owned())))
+        );
+        assert_eq!(command(Command::Generate).arguments(&args(&["x"])), None);
+    }
+
+    #[test]
+    fn test_synopsis() {
+        assert_eq!(
+            command(Command::Config).synopsis(),
+            "config [options] [print|check|export]"
+        );
+        assert_eq!(
+            command(Command::Completions).synopsis(),
+            "completions [options] bash|zsh|fish"
+        );
+        assert_eq!(
+            command(Command::Wordlist).synopsis(),
+            "wordlist [options] [check] [FILE]"
+        );
+    }
+
+    #[test]
+    fn test_completions_contain_options_and_choices() {
+        for (_, shell) in Shell::NAME_MEMBER_ARR {
+            let script = completions(*shell);
+            for option in options() {
+                assert!(script.contains(option.long), "{shell}: {}", option.long);
+            }
+            for choice in choices() {
+                assert!(script.contains(choice), "{shell}: {choice}");
+            }
+            for command in commands() {
+                assert!(
+                    script.contains(command.name()),
+                    "{shell}: {}",
+                    command.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_zsh_escape() {
+        assert_eq!(zsh_escape("a [b]: 'c'"), r"a \[b\]\: '\''c'\''");
+    }
+
+    #[test]
+    fn test_man_page() {
+        let man = man_page();
+        assert!(man.starts_with(".TH FMN\\-PASSGEN 1"));
+        assert!(man.contains(r"\fB\-W\fR, \fB\-\-word\-transformation\fR"));
+        for choice in choices() {
+            assert!(man.contains(&roff_escape(choice)), "{choice}");
+        }
+    }
+
+    #[test]
+    fn test_roff_escape() {
+        assert_eq!(roff_escape(r"a-b\c"), r"a\-b\ec");
+        assert_eq!(roff_escape(".TH"), r"\&.TH");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("os-rng"), "os-rng");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("!@$"), "'!@$'");
+        assert_eq!(shell_quote("a'b"), r"'a'\''b'");
+    }
+
+    /// Undo [`shell_quote`] well enough for the exported options.
+    fn shell_unquote(text: &str) -> String {
+        text.replace(r"'\''", "\u{0}")
+            .replace('\'', "")
+            .replace('\u{0}', "'")
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let config = ConfigBuilder::new()
+            .slot_wordlists(Some("adj:2+noun".to_owned()))
+            .word_transformation(Some("random-upper-lower".to_owned()))
+            .padding_type(Some("adaptive-front".to_owned()))
+            .padding_characters(Some("'-".to_owned()))
+            .digit_placement(Some("attached".to_owned()))
+            .digit_class(Some("hex".to_owned()))
+            .digit_characters(Some("ACEF".to_owned()))
+            .padding_fill(Some("random".to_owned()))
+            .padding_back_characters(Some("+=".to_owned()))
+            .separator_characters(Some(String::new()))
+            .separator_type(Some("sequence".to_owned()))
+            .separator_sequence(Some("-.-".to_owned()))
+            .joined_words(Some("prefix-free".to_owned()))
+            .avoid_ambiguous(Some("true".to_owned()))
+            .blocklist(Some("true".to_owned()))
+            .rng_type(Some("csprng".to_owned()))
+            .min_symbols(Some("2".to_owned()))
+            .max_length(Some("40".to_owned()))
+            .build()
+            .unwrap();
+        let exported = export(&config).unwrap();
+        let args = exported
+            .split(' ')
+            .skip(2)
+            .map(shell_unquote)
+            .collect::<Vec<String>>();
+        let matches = getopts_options().parse(args).unwrap();
+        assert_eq!(config_builder(&matches).unwrap().build().unwrap(), config);
+    }
+    #[test]
+    fn test_export_refuses_files() {
+        let custom_wordlist = ConfigBuilder::new()
+            .custom_wordlist(Some("mango\nkiwi".to_owned()))
+            .build()
+            .unwrap();
+        assert!(export(&custom_wordlist).is_err());
+        let excluded_words = ConfigBuilder::new()
+            .excluded_words(Some("apple".to_owned()))
+            .build()
+            .unwrap();
+        assert!(export(&excluded_words).is_err());
+        assert!(export(&Config::default()).is_ok());
+    }
+}
@@ -1,22 +1,49 @@
 #![expect(missing_docs, reason = "derive macros could use some more polish")]
 //! Configuration and validation.
+use std::fmt;
+use std::sync::Arc;
+
 use config_builder_derive::ConfigBuilder;
 
 use crate::consts::default;
+use crate::consts::mobile;
+use crate::keyboard;
+use crate::password_maker::builtin_wordlist_names;
+use crate::policy::Policy;
+use crate::types::DigitClass;
+use crate::types::DigitPlacement;
 use crate::types::Integer;
+use crate::types::JoinedWords;
+use crate::types::KeyboardLayout;
+use crate::types::PaddingFill;
 use crate::types::PaddingType;
+use crate::types::PartOfSpeech;
+use crate::types::Preset;
 use crate::types::RngType;
+use crate::types::SeparatorType;
+use crate::types::SlotSource;
 use crate::types::StrEnum;
 use crate::types::ValidationError;
+use crate::types::WordSource;
 use crate::types::WordTransformationType;
+use crate::wordlist;
+use crate::wordlist::is_ambiguous;
 
 /// All of the configuration necessary to make passwords.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
     /// how many passwords to make
     pub count: u8,
+    /// name of the built-in wordlist to choose words from
+    pub wordlist_name: &'static str,
+    /// words read from a custom wordlist, sorted and without duplicates, see [`WordSource::Custom`]
+    pub custom_wordlist: Arc<[Box<str>]>,
     /// number of words to choose
     pub word_count: u8,
+    /// parts of speech to fill in order, which sets the word count, or none to draw every word from the wordlist
+    pub template: Vec<PartOfSpeech>,
+    /// the wordlists each word is drawn from in turn, which sets the word count, or none to draw every word from the wordlist
+    pub slot_wordlists: Vec<Vec<SlotSource>>,
     /// minimum length of a chosen word
     pub word_min_length: u8,
     /// maximum length of a chosen word
@@ -27,16 +54,48 @@ pub struct Config {
     pub digits_before: u8,
     /// number of digits to append
     pub digits_after: u8,
+    /// where to put the digits
+    pub digit_placement: DigitPlacement,
+    /// character class from which to choose the digits, unless `digit_characters` is given
+    pub digit_class: DigitClass,
+    /// list of characters from which to choose the digits
+    pub digit_characters: Vec<char>,
     /// how to apply padding
     pub padding_type: PaddingType,
     /// how much to pad
     pub padding_length: u8,
     /// list of characters from which to choose the padding character
     pub padding_characters: Vec<char>,
+    /// how to choose padding characters
+    pub padding_fill: PaddingFill,
+    /// list of characters from which to choose front padding, unless [`PaddingFill::Same`]
+    pub padding_front_characters: Vec<char>,
+    /// list of characters from which to choose back padding, unless [`PaddingFill::Same`]
+    pub padding_back_characters: Vec<char>,
     /// list of characters from which to choose the separator character
     pub separator_characters: Vec<char>,
+    /// how to choose separators
+    pub separator_type: SeparatorType,
+    /// separators to use in order, for [`SeparatorType::Sequence`]
+    pub separator_sequence: Vec<char>,
+    /// what to do about words which read as others when run together, see [`Self::joins_words`]
+    pub joined_words: JoinedWords,
+    /// whether to leave out look-alike characters and words containing them
+    pub avoid_ambiguous: bool,
+    /// the preset whose defaults were used, see [`crate::consts::mobile`]
+    pub preset: Preset,
+    /// the keyboard passwords are typed on
+    pub keyboard: KeyboardLayout,
+    /// whether to leave out characters and words which are costly to type on [`Self::keyboard`]
+    pub easy_typing: bool,
+    /// whether to leave out the words on the built-in blocklist
+    pub blocklist: bool,
+    /// more words to leave out, lowercase and sorted
+    pub excluded_words: Vec<Box<str>>,
     /// method of random number generation
     pub rng_type: RngType,
+    /// rules every password must follow
+    pub policy: Policy,
 }
 
 impl Default for Config {
@@ -46,21 +105,213 @@ impl Default for Config {
     }
 }
 
+/// One `name: value` line per setting, named after the [`ConfigBuilder`] fields.
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "wordlist_name: {}", self.wordlist_name)?;
+        writeln!(f, "custom_wordlist: {} words", self.custom_wordlist.len())?;
+        writeln!(f, "word_count: {}", self.word_count)?;
+        writeln!(
+            f,
+            "template: \"{}\"",
+            self.template
+                .iter()
+                .map(StrEnum::to_static_str)
+                .collect::<Vec<&str>>()
+                .join(" ")
+        )?;
+        writeln!(
+            f,
+            "slot_wordlists: \"{}\"",
+            slot_wordlists_text(&self.slot_wordlists)
+        )?;
+        writeln!(f, "word_min_length: {}", self.word_min_length)?;
+        writeln!(f, "word_max_length: {}", self.word_max_length)?;
+        writeln!(f, "word_transformation: {}", self.word_transformation)?;
+        writeln!(f, "digits_before: {}", self.digits_before)?;
+        writeln!(f, "digits_after: {}", self.digits_after)?;
+        writeln!(f, "digit_placement: {}", self.digit_placement)?;
+        writeln!(f, "digit_class: {}", self.digit_class)?;
+        writeln!(
+            f,
+            "digit_characters: \"{}\"",
+            self.digit_characters.iter().collect::<String>()
+        )?;
+        writeln!(f, "padding_type: {}", self.padding_type)?;
+        writeln!(f, "padding_length: {}", self.padding_length)?;
+        writeln!(
+            f,
+            "padding_characters: \"{}\"",
+            self.padding_characters.iter().collect::<String>()
+        )?;
+        writeln!(f, "padding_fill: {}", self.padding_fill)?;
+        writeln!(
+            f,
+            "padding_front_characters: \"{}\"",
+            self.padding_front_characters.iter().collect::<String>()
+        )?;
+        writeln!(
+            f,
+            "padding_back_characters: \"{}\"",
+            self.padding_back_characters.iter().collect::<String>()
+        )?;
+        writeln!(
+            f,
+            "separator_characters: \"{}\"",
+            self.separator_characters.iter().collect::<String>()
+        )?;
+        writeln!(f, "separator_type: {}", self.separator_type)?;
+        writeln!(
+            f,
+            "separator_sequence: \"{}\"",
+            self.separator_sequence.iter().collect::<String>()
+        )?;
+        writeln!(f, "joined_words: {}", self.joined_words)?;
+        writeln!(f, "avoid_ambiguous: {}", self.avoid_ambiguous)?;
+        writeln!(f, "preset: {}", self.preset)?;
+        writeln!(f, "keyboard: {}", self.keyboard)?;
+        writeln!(f, "easy_typing: {}", self.easy_typing)?;
+        writeln!(f, "blocklist: {}", self.blocklist)?;
+        writeln!(f, "excluded_words: \"{}\"", self.excluded_words.join(" "))?;
+        writeln!(f, "rng_type: {}", self.rng_type)?;
+        writeln!(f, "min_uppercase: {}", self.policy.min_uppercase)?;
+        writeln!(f, "min_lowercase: {}", self.policy.min_lowercase)?;
+        writeln!(f, "min_digits: {}", self.policy.min_digits)?;
+        writeln!(f, "min_symbols: {}", self.policy.min_symbols)?;
+        writeln!(f, "max_length: {}", self.policy.max_length)?;
+        writeln!(f, "max_consecutive: {}", self.policy.max_consecutive)
+    }
+}
+
+impl Config {
+    /// The number of digits in a password with `word_count` words.
+    #[must_use]
+    pub const fn digit_count(&self, word_count: usize) -> usize {
+        let after = match self.digit_placement {
+            DigitPlacement::Ends | DigitPlacement::RandomGap | DigitPlacement::Attached => 1,
+            DigitPlacement::EveryGap => word_count,
+        };
+        self.digits_before as usize + after * self.digits_after as usize
+    }
+    /// The number of separately placed groups of digits in a password with `word_count` words.
+    #[must_use]
+    pub fn digit_group_count(&self, word_count: usize) -> usize {
+        let after = match self.digit_placement {
+            DigitPlacement::Ends | DigitPlacement::RandomGap | DigitPlacement::Attached => 1,
+            DigitPlacement::EveryGap => word_count,
+        };
+        usize::from(self.digits_before > 0) + after * usize::from(self.digits_after > 0)
+    }
+    /// The number of parts, words and groups of digits that are not attached to a word,
+    /// in a password with `word_count` words.
+    #[must_use]
+    pub fn part_count(&self, word_count: usize) -> usize {
+        if self.digit_placement == DigitPlacement::Attached && word_count > 0 {
+            word_count
+        } else {
+            word_count + self.digit_group_count(word_count)
+        }
+    }
+    /// The characters front and back padding are made from, depending on the [`PaddingFill`].
+    #[must_use]
+    pub fn padding_alphabets(&self) -> [&[char]; 2] {
+        match self.padding_fill {
+            PaddingFill::Same => [&self.padding_characters, &self.padding_characters],
+            PaddingFill::Independent | PaddingFill::Random => [
+                &self.padding_front_characters,
+                &self.padding_back_characters,
+            ],
+        }
+    }
+    /// Whether any words are blocked, see [`Self::blocked_words`].
+    #[must_use]
+    pub const fn blocks_words(&self) -> bool {
+        self.blocklist || !self.excluded_words.is_empty()
+    }
+    /// Every word to leave out, along with offensive combinations of adjacent words made from them.
+    pub fn blocked_words(&self) -> impl Iterator<Item = &str> {
+        let builtin: &[Box<str>] = if self.blocklist {
+            wordlist::default_blocklist()
+        } else {
+            &[]
+        };
+        builtin
+            .iter()
+            .chain(&self.excluded_words)
+            .map(AsRef::as_ref)
+    }
+    /// The characters separators are made from, depending on the [`SeparatorType`].
+    #[must_use]
+    pub fn separator_alphabet(&self) -> &[char] {
+        match self.separator_type {
+            SeparatorType::Single | SeparatorType::PerGap => &self.separator_characters,
+            SeparatorType::Sequence => &self.separator_sequence,
+        }
+    }
+    /// Whether words run together, with neither a separator nor a change of case to tell where one ends.
+    ///
+    /// Digits may still fall between them, but are not counted on to.
+    #[must_use]
+    pub fn joins_words(&self) -> bool {
+        self.word_count > 1
+            && self.separator_alphabet().is_empty()
+            && matches!(
+                self.word_transformation,
+                WordTransformationType::None
+                    | WordTransformationType::Lower
+                    | WordTransformationType::Upper
+                    | WordTransformationType::CapitalizeFirstWord
+                    | WordTransformationType::RandomUpperLower
+            )
+    }
+    /// Whether words which start another word are left out, see [`JoinedWords::PrefixFree`].
+    #[must_use]
+    pub fn drops_prefixes(&self) -> bool {
+        self.joined_words == JoinedWords::PrefixFree && self.joins_words()
+    }
+}
+
 /// Provide a way in which to create a validated [`Config`].
 #[derive(ConfigBuilder, Debug, Default)]
 pub struct ConfigBuilder {
     count: Option<String>,
+    wordlist_name: Option<String>,
+    custom_wordlist: Option<String>,
     word_count: Option<String>,
+    template: Option<String>,
+    slot_wordlists: Option<String>,
     word_min_length: Option<String>,
     word_max_length: Option<String>,
     word_transformation: Option<String>,
     digits_before: Option<String>,
     digits_after: Option<String>,
+    digit_placement: Option<String>,
+    digit_class: Option<String>,
+    digit_characters: Option<String>,
     padding_type: Option<String>,
     padding_length: Option<String>,
     padding_characters: Option<String>,
+    padding_fill: Option<String>,
+    padding_front_characters: Option<String>,
+    padding_back_characters: Option<String>,
     separator_characters: Option<String>,
+    separator_type: Option<String>,
+    separator_sequence: Option<String>,
+    joined_words: Option<String>,
+    avoid_ambiguous: Option<String>,
+    preset: Option<String>,
+    keyboard: Option<String>,
+    easy_typing: Option<String>,
+    blocklist: Option<String>,
+    excluded_words: Option<String>,
     rng_type: Option<String>,
+    min_uppercase: Option<String>,
+    min_lowercase: Option<String>,
+    min_digits: Option<String>,
+    min_symbols: Option<String>,
+    max_length: Option<String>,
+    max_consecutive: Option<String>,
 }
 
 /// Ensure an [`Integer`] is between `min` and `max`.
@@ -101,6 +352,142 @@ fn validate_enum<T: StrEnum>(value: Option<String>) -> Result<T, ValidationError
     })
 }
 
+/// Ensure `value` names a built-in wordlist, ignoring case.
+/// If no `value` is provided, return the default wordlist's name
+fn validate_wordlist_name(value: Option<String>) -> Result<&'static str, ValidationError> {
+    let names = builtin_wordlist_names();
+    value.map_or(Ok(names[0]), |inner| {
+        names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(&inner))
+            .copied()
+            .ok_or_else(|| {
+                ValidationError::InvalidEnum(format!(
+                    "`{inner}` is not a valid wordlist. Possible choices: {}",
+                    names.join(", ")
+                ))
+            })
+    })
+}
+
+/// Ensure `value` is a whitespace separated list of [`PartOfSpeech`] names, ignoring case.
+/// If no `value` is provided, return an empty template
+fn validate_template(value: Option<String>) -> Result<Vec<PartOfSpeech>, ValidationError> {
+    value.map_or(Ok(Vec::new()), |inner| {
+        inner
+            .split_whitespace()
+            .map(|part| PartOfSpeech::to_member(&part.to_ascii_lowercase()).copied())
+            .collect()
+    })
+}
+
+/// Ensure `name` is a [`WordSource`]: a built-in wordlist, a [`PartOfSpeech`], or `custom` if `has_custom`,
+/// ignoring case.
+fn validate_word_source(name: &str, has_custom: bool) -> Result<WordSource, ValidationError> {
+    let lowercase = name.to_ascii_lowercase();
+    if let Some(name) = builtin_wordlist_names()
+        .iter()
+        .find(|builtin| **builtin == lowercase)
+    {
+        return Ok(WordSource::Builtin(name));
+    }
+    if let Ok(part) = PartOfSpeech::to_member(&lowercase) {
+        return Ok(WordSource::Part(*part));
+    }
+    if lowercase == "custom" && has_custom {
+        return Ok(WordSource::Custom);
+    }
+    let mut choices = builtin_wordlist_names().to_vec();
+    choices.extend(PartOfSpeech::NAMES);
+    if has_custom {
+        choices.push("custom");
+    }
+    Err(ValidationError::InvalidEnum(format!(
+        "`{name}` is not a valid slot wordlist. Possible choices: {}",
+        choices.join(", ")
+    )))
+}
+
+/// Ensure `value` is a whitespace separated list of word slots, each a `+` separated mix of [`WordSource`]s,
+/// optionally followed by `:` and a weight from 1 to 255.
+/// If no `value` is provided, return no slots
+fn validate_slot_wordlists(
+    value: Option<String>,
+    has_custom: bool,
+) -> Result<Vec<Vec<SlotSource>>, ValidationError> {
+    value.map_or(Ok(Vec::new()), |inner| {
+        inner
+            .split_whitespace()
+            .map(|slot| {
+                slot.split('+')
+                    .map(|source| {
+                        let (name, weight) = source
+                            .split_once(':')
+                            .map_or((source, None), |(name, weight)| {
+                                (name, Some(weight.to_owned()))
+                            });
+                        Ok(SlotSource {
+                            source: validate_word_source(name, has_custom)?,
+                            weight: validate_int::<u8>(weight, 1, 255, 1)?,
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    })
+}
+
+/// `slots` as [`ConfigBuilder::slot_wordlists`] accepts them.
+#[must_use]
+pub fn slot_wordlists_text(slots: &[Vec<SlotSource>]) -> String {
+    slots
+        .iter()
+        .map(|slot| {
+            slot.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join("+")
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Turn the text of a custom wordlist into a sorted list of words with no duplicates.
+/// If no `value` is provided, return an empty list
+fn parse_custom_wordlist(value: Option<String>) -> Arc<[Box<str>]> {
+    let mut words = wordlist::parse(&value.unwrap_or_default());
+    words.sort_unstable();
+    words.dedup();
+    words.into()
+}
+
+/// Ensure `value` is either `true` or `false`.
+/// If no `value` is provided, return `false`
+fn validate_bool(value: Option<String>) -> Result<bool, ValidationError> {
+    value.map_or(Ok(false), |inner| {
+        match inner.to_ascii_lowercase().as_str() {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(ValidationError::InvalidEnum(format!(
+                "`{inner}` is not a valid bool. Possible choices: true, false"
+            ))),
+        }
+    })
+}
+
+/// Turn a [`String`] of whitespace separated words into a sorted list of lowercase words with no duplicates.
+/// If no `value` is provided, return an empty list
+fn parse_words(value: Option<String>) -> Vec<Box<str>> {
+    let mut result = value
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|word| word.to_lowercase().into_boxed_str())
+        .collect::<Vec<Box<str>>>();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
 /// Turn a [`String`] into a [`Vec<char>`] with no duplicates.
 /// If no `value` is provided, return `default`
 fn uniquify_chars(value: Option<String>, default: &[char]) -> Vec<char> {
@@ -115,6 +502,41 @@ fn uniquify_chars(value: Option<String>, default: &[char]) -> Vec<char> {
     )
 }
 
+/// Expose the string parsers to the fuzz targets in `fuzz/`.
+#[cfg(fuzzing)]
+pub mod fuzzing {
+    use super::Integer;
+    use super::StrEnum;
+    use super::ValidationError;
+
+    /// See [`super::validate_int`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ValidationError`] if `value` is not an integer between `min` and `max`.
+    pub fn validate_int<T: Integer>(
+        value: Option<String>,
+        min: T,
+        max: T,
+        default: T,
+    ) -> Result<T, ValidationError> {
+        super::validate_int(value, min, max, default)
+    }
+    /// See [`super::validate_enum`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ValidationError`] if `value` does not name a member of `T`.
+    pub fn validate_enum<T: StrEnum>(value: Option<String>) -> Result<T, ValidationError> {
+        super::validate_enum(value)
+    }
+    /// See [`super::uniquify_chars`].
+    #[must_use]
+    pub fn uniquify_chars(value: Option<String>, default: &[char]) -> Vec<char> {
+        super::uniquify_chars(value, default)
+    }
+}
+
 /// Setters are auto generated by [`strenum_derive::StrEnum`].
 impl ConfigBuilder {
     #[must_use]
@@ -122,15 +544,48 @@ impl ConfigBuilder {
     pub fn new() -> Self {
         Self::default()
     }
+    /// Fill in the settings `preset` has its own defaults for, unless they were given.
+    fn fill_preset(&mut self, preset: Preset) {
+        match preset {
+            Preset::Desktop => {}
+            Preset::Mobile => {
+                self.word_transformation
+                    .get_or_insert_with(|| mobile::WORD_TRANSFORMATION.to_static_str().to_owned());
+                self.digit_placement
+                    .get_or_insert_with(|| mobile::DIGIT_PLACEMENT.to_static_str().to_owned());
+                self.keyboard
+                    .get_or_insert_with(|| mobile::KEYBOARD.to_static_str().to_owned());
+                self.easy_typing
+                    .get_or_insert_with(|| mobile::EASY_TYPING.to_string());
+            }
+        }
+    }
     /// Construct a [`Config`]
     ///
     /// # Errors
     ///
     /// Will return [`ValidationError`] if any config member fails validation.
-    pub fn build(self) -> Result<Config, ValidationError> {
+    #[expect(clippy::too_many_lines, reason = "it validates every field in turn")]
+    pub fn build(mut self) -> Result<Config, ValidationError> {
         // TODO add constraints to consts.rs
+        let preset = validate_enum::<Preset>(self.preset.take())?;
+        self.fill_preset(preset);
         let count = validate_int::<u8>(self.count, 1, 255, default::COUNT)?;
-        let word_count = validate_int::<u8>(self.word_count, 0, 32, default::WORD_COUNT)?;
+        let wordlist_name = validate_wordlist_name(self.wordlist_name)?;
+        let custom_wordlist = parse_custom_wordlist(self.custom_wordlist);
+        let template = validate_template(self.template)?;
+        let slot_wordlists =
+            validate_slot_wordlists(self.slot_wordlists, !custom_wordlist.is_empty())?;
+        let word_count = match (template.len(), slot_wordlists.len()) {
+            (0, 0) => validate_int::<u8>(self.word_count, 0, 32, default::WORD_COUNT)?,
+            (slots, 0) | (0, slots) => validate_int::<u8>(Some(slots.to_string()), 0, 32, 0)?,
+            _ => {
+                return Err(ValidationError::InvalidEnum(
+                    "a template and slot wordlists can not be combined, name the parts of speech in the slot wordlists instead"
+                        .to_owned(),
+                ));
+            }
+        };
         let word_min_length =
             validate_int::<u8>(self.word_min_length, 1, 255, default::WORD_MIN_LENGTH)?;
         let word_max_length = validate_int::<u8>(
@@ -143,11 +598,60 @@ impl ConfigBuilder {
             validate_enum::<WordTransformationType>(self.word_transformation)?;
         let digits_before = validate_int::<u8>(self.digits_before, 0, 255, default::DIGITS_BEFORE)?;
         let digits_after = validate_int::<u8>(self.digits_after, 0, 255, default::DIGITS_AFTER)?;
-        let padding_characters = uniquify_chars(self.padding_characters, &default::SYMBOL_ALPHABET);
-        let padding_type = if padding_characters.is_empty() {
-            PaddingType::None
+        let avoid_ambiguous = validate_bool(self.avoid_ambiguous)?;
+        let blocklist = validate_bool(self.blocklist)?;
+        let excluded_words = parse_words(self.excluded_words);
+        let keyboard = validate_enum::<KeyboardLayout>(self.keyboard)?;
+        let easy_typing = validate_bool(self.easy_typing)?;
+        let unambiguous = |mut chars: Vec<char>| {
+            if avoid_ambiguous {
+                chars.retain(|&c| !is_ambiguous(c));
+            }
+            chars
+        };
+        // symbols only, digits are typed however costly they are
+        let easy = |mut chars: Vec<char>| {
+            if easy_typing {
+                chars.retain(|&c| keyboard::is_easy(c, keyboard));
+            }
+            unambiguous(chars)
+        };
+        let digit_placement = validate_enum::<DigitPlacement>(self.digit_placement)?;
+        let digit_class = validate_enum::<DigitClass>(self.digit_class)?;
+        let digit_characters = unambiguous(uniquify_chars(
+            self.digit_characters,
+            &digit_class.characters(),
+        ));
+        let padding_characters = easy(uniquify_chars(
+            self.padding_characters,
+            &default::SYMBOL_ALPHABET,
+        ));
+        let padding_fill = validate_enum::<PaddingFill>(self.padding_fill)?;
+        let padding_front_characters = easy(uniquify_chars(
+            self.padding_front_characters,
+            &padding_characters,
+        ));
+        let padding_back_characters = easy(uniquify_chars(
+            self.padding_back_characters,
+            &padding_characters,
+        ));
+        let padding_type = validate_enum::<PaddingType>(self.padding_type)?;
+        // padding with nothing to pad with is no padding at all
+        let alphabets = match padding_fill {
+            PaddingFill::Same => [&padding_characters, &padding_characters],
+            PaddingFill::Independent | PaddingFill::Random => {
+                [&padding_front_characters, &padding_back_characters]
+            }
+        };
+        let padding_type = if padding_type
+            .ends()
+            .into_iter()
+            .zip(alphabets)
+            .any(|(pads, alphabet)| pads && !alphabet.is_empty())
+        {
+            padding_type
         } else {
-            validate_enum::<PaddingType>(self.padding_type)?
+            PaddingType::None
         };
         let padding_length = validate_int::<u8>(self.padding_length, 0, 255, {
             match padding_type {
@@ -161,30 +665,131 @@ impl ConfigBuilder {
                 PaddingType::None => 0,
             }
         })?;
-        let separator_characters =
-            uniquify_chars(self.separator_characters, &default::SYMBOL_ALPHABET);
+        let separator_characters = easy(uniquify_chars(
+            self.separator_characters,
+            &default::SYMBOL_ALPHABET,
+        ));
+        let separator_type = validate_enum::<SeparatorType>(self.separator_type)?;
+        // unlike the other character lists, order and repetition matter here
+        let separator_sequence = easy(
+            self.separator_sequence
+                .map(|s| s.chars().collect())
+                .unwrap_or_default(),
+        );
+        if separator_type == SeparatorType::Sequence && separator_sequence.is_empty() {
+            return Err(ValidationError::InvalidEnum(
+                "the separator sequence is empty, or has no characters left once ambiguous or hard to type ones are removed"
+                    .to_owned(),
+            ));
+        }
+        let joined_words = validate_enum::<JoinedWords>(self.joined_words)?;
         let rng_type = validate_enum::<RngType>(self.rng_type)?;
+        let policy = Policy {
+            min_uppercase: validate_int::<u8>(self.min_uppercase, 0, 255, 0)?,
+            min_lowercase: validate_int::<u8>(self.min_lowercase, 0, 255, 0)?,
+            min_digits: validate_int::<u8>(self.min_digits, 0, 255, 0)?,
+            min_symbols: validate_int::<u8>(self.min_symbols, 0, 255, 0)?,
+            max_length: validate_int::<u8>(self.max_length, 0, 255, 0)?,
+            max_consecutive: validate_int::<u8>(self.max_consecutive, 0, 255, 0)?,
+        };
 
         Ok(Config {
             count,
+            wordlist_name,
+            custom_wordlist,
             word_count,
+            template,
+            slot_wordlists,
             word_min_length,
             word_max_length,
             word_transformation,
             digits_before,
             digits_after,
+            digit_placement,
+            digit_class,
+            digit_characters,
             padding_type,
             padding_length,
             padding_characters,
+            padding_fill,
+            padding_front_characters,
+            padding_back_characters,
             separator_characters,
+            separator_type,
+            separator_sequence,
+            joined_words,
+            avoid_ambiguous,
+            preset,
+            keyboard,
+            easy_typing,
+            blocklist,
+            excluded_words,
             rng_type,
+            policy,
         })
     }
 }
 
+/// Start a [`ConfigBuilder`] from every setting of `config`.
+///
+/// Building it unchanged gives `config` back, except that the character lists come back sorted,
+/// which changes neither which characters may be drawn nor how likely each is.
+impl From<&Config> for ConfigBuilder {
+    fn from(config: &Config) -> Self {
+        let chars = |chars: &[char]| Some(chars.iter().collect());
+        let words = |words: &[Box<str>], separator: &str| Some(words.join(separator));
+        Self::new()
+            .count(Some(config.count.to_string()))
+            .wordlist_name(Some(config.wordlist_name.to_owned()))
+            .custom_wordlist(words(&config.custom_wordlist, "\n"))
+            .word_count(Some(config.word_count.to_string()))
+            .template(Some(
+                config
+                    .template
+                    .iter()
+                    .map(StrEnum::to_static_str)
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+            ))
+            .slot_wordlists(Some(slot_wordlists_text(&config.slot_wordlists)))
+            .word_min_length(Some(config.word_min_length.to_string()))
+            .word_max_length(Some(config.word_max_length.to_string()))
+            .word_transformation(Some(config.word_transformation.to_static_str().to_owned()))
+            .digits_before(Some(config.digits_before.to_string()))
+            .digits_after(Some(config.digits_after.to_string()))
+            .digit_placement(Some(config.digit_placement.to_static_str().to_owned()))
+            .digit_class(Some(config.digit_class.to_static_str().to_owned()))
+            .digit_characters(chars(&config.digit_characters))
+            .padding_type(Some(config.padding_type.to_static_str().to_owned()))
+            .padding_length(Some(config.padding_length.to_string()))
+            .padding_characters(chars(&config.padding_characters))
+            .padding_fill(Some(config.padding_fill.to_static_str().to_owned()))
+            .padding_front_characters(chars(&config.padding_front_characters))
+            .padding_back_characters(chars(&config.padding_back_characters))
+            .separator_characters(chars(&config.separator_characters))
+            .separator_type(Some(config.separator_type.to_static_str().to_owned()))
+            .separator_sequence(chars(&config.separator_sequence))
+            .joined_words(Some(config.joined_words.to_static_str().to_owned()))
+            .avoid_ambiguous(Some(config.avoid_ambiguous.to_string()))
+            .preset(Some(config.preset.to_static_str().to_owned()))
+            .keyboard(Some(config.keyboard.to_static_str().to_owned()))
+            .easy_typing(Some(config.easy_typing.to_string()))
+            .blocklist(Some(config.blocklist.to_string()))
+            .excluded_words(words(&config.excluded_words, " "))
+            .rng_type(Some(config.rng_type.to_static_str().to_owned()))
+            .min_uppercase(Some(config.policy.min_uppercase.to_string()))
+            .min_lowercase(Some(config.policy.min_lowercase.to_string()))
+            .min_digits(Some(config.policy.min_digits.to_string()))
+            .min_symbols(Some(config.policy.min_symbols.to_string()))
+            .max_length(Some(config.policy.max_length.to_string()))
+            .max_consecutive(Some(config.policy.max_consecutive.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::consts::DIGIT_ALPHABET;
     use std::mem::discriminant;
 
     #[test]
@@ -210,10 +815,26 @@ mod tests {
             config.separator_characters,
             default::SYMBOL_ALPHABET.to_vec()
         );
+        assert_eq!(config.padding_fill, PaddingFill::Same);
+        assert_eq!(config.padding_front_characters, config.padding_characters);
+        assert_eq!(config.padding_back_characters, config.padding_characters);
+        assert_eq!(config.separator_type, SeparatorType::Single);
+        assert!(config.separator_sequence.is_empty());
         assert_eq!(
             discriminant(&config.rng_type),
             discriminant(&RngType::default())
         );
+        assert!(config.policy.is_empty());
+    }
+
+    #[test]
+    fn test_display_names_every_field() {
+        let display = Config::default().to_string();
+        let names = display
+            .lines()
+            .map(|line| line.split_once(": ").unwrap().0)
+            .collect::<Vec<&str>>();
+        assert_eq!(names, ConfigBuilder::FIELD_NAMES);
     }
 
     #[test]
@@ -236,4 +857,409 @@ mod tests {
             .unwrap();
         assert_eq!(config.padding_length, default::PADDING_LENGTH_ADAPTIVE);
     }
+
+    #[test]
+    fn test_padding_alphabets() {
+        let config = ConfigBuilder::new()
+            .padding_characters(Some("ab".to_owned()))
+            .padding_back_characters(Some("cc".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.padding_front_characters, ['a', 'b']);
+        assert_eq!(config.padding_alphabets(), [&['a', 'b'][..], &['a', 'b']]);
+        let config = ConfigBuilder::new()
+            .padding_characters(Some("ab".to_owned()))
+            .padding_fill(Some("independent".to_owned()))
+            .padding_back_characters(Some("cc".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.padding_alphabets(), [&['a', 'b'][..], &['c']]);
+    }
+
+    #[test]
+    fn test_padding_type_needs_characters_at_its_ends() {
+        let padding_type = |padding_type: &str, fill: &str, front: &str, back: &str| {
+            ConfigBuilder::new()
+                .padding_type(Some(padding_type.to_owned()))
+                .padding_characters(Some(String::new()))
+                .padding_fill(Some(fill.to_owned()))
+                .padding_front_characters(Some(front.to_owned()))
+                .padding_back_characters(Some(back.to_owned()))
+                .build()
+                .unwrap()
+                .padding_type
+        };
+        // the same fill only draws from padding-characters
+        assert_eq!(
+            padding_type("fixed-back", "same", "<", ">"),
+            PaddingType::None
+        );
+        // front or back only padding only needs characters at that end
+        assert_eq!(
+            padding_type("fixed-back", "independent", "", ">"),
+            PaddingType::FixedBack
+        );
+        assert_eq!(
+            padding_type("adaptive-front", "random", "<", ""),
+            PaddingType::AdaptiveFront
+        );
+        assert_eq!(
+            padding_type("fixed-back", "random", "<", ""),
+            PaddingType::None
+        );
+        assert_eq!(
+            padding_type("fixed-front", "independent", "", ">"),
+            PaddingType::None
+        );
+        // padding both ends needs characters at either
+        assert_eq!(
+            padding_type("fixed-both", "independent", "", ">"),
+            PaddingType::FixedBoth
+        );
+        assert_eq!(
+            padding_type("fixed-both", "random", "", ""),
+            PaddingType::None
+        );
+    }
+
+    #[test]
+    fn test_digit_characters() {
+        assert_eq!(Config::default().digit_characters, DIGIT_ALPHABET);
+        let config = ConfigBuilder::new()
+            .digit_class(Some("HEX".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.digit_characters.iter().collect::<String>(),
+            "0123456789abcdef"
+        );
+        // explicit characters win over the class
+        let config = ConfigBuilder::new()
+            .digit_class(Some("upper".to_owned()))
+            .digit_characters(Some("98765432".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.digit_characters.iter().collect::<String>(),
+            "23456789"
+        );
+    }
+
+    #[test]
+    fn test_digit_placement_counts() {
+        let make = |placement: &str| {
+            ConfigBuilder::new()
+                .digits_before(Some("1".to_owned()))
+                .digits_after(Some("2".to_owned()))
+                .digit_placement(Some(placement.to_owned()))
+                .build()
+                .unwrap()
+        };
+        let counts = |config: &Config, word_count| {
+            (
+                config.digit_count(word_count),
+                config.digit_group_count(word_count),
+                config.part_count(word_count),
+            )
+        };
+        assert_eq!(counts(&make("ends"), 4), (3, 2, 6));
+        assert_eq!(counts(&make("random-gap"), 4), (3, 2, 6));
+        assert_eq!(counts(&make("attached"), 4), (3, 2, 4));
+        assert_eq!(counts(&make("attached"), 0), (3, 2, 2));
+        assert_eq!(counts(&make("every-gap"), 4), (9, 5, 9));
+    }
+
+    #[test]
+    fn test_separator_sequence_keeps_order() {
+        let config = ConfigBuilder::new()
+            .separator_type(Some("sequence".to_owned()))
+            .separator_sequence(Some("-.-_".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.separator_sequence, ['-', '.', '-', '_']);
+        assert_eq!(config.separator_alphabet(), ['-', '.', '-', '_']);
+        assert_eq!(
+            Config::default().separator_alphabet(),
+            default::SYMBOL_ALPHABET
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let configs = [
+            Config::default(),
+            ConfigBuilder::new()
+                .preset(Some("mobile".to_owned()))
+                .build()
+                .unwrap(),
+            ConfigBuilder::new()
+                .custom_wordlist(Some("mango\nkiwi\n".to_owned()))
+                .slot_wordlists(Some("custom:3+eff-large noun".to_owned()))
+                .excluded_words(Some("apple pear".to_owned()))
+                .separator_type(Some("sequence".to_owned()))
+                .separator_sequence(Some("-.-".to_owned()))
+                .padding_fill(Some("random".to_owned()))
+                .padding_front_characters(Some("<[".to_owned()))
+                .avoid_ambiguous(Some("true".to_owned()))
+                .max_length(Some("40".to_owned()))
+                .build()
+                .unwrap(),
+            ConfigBuilder::new()
+                .template(Some("adj noun".to_owned()))
+                .padding_characters(Some(String::new()))
+                .build()
+                .unwrap(),
+        ];
+        let sorted = |mut config: Config| {
+            config.digit_characters.sort_unstable();
+            config.padding_characters.sort_unstable();
+            config.padding_front_characters.sort_unstable();
+            config.padding_back_characters.sort_unstable();
+            config.separator_characters.sort_unstable();
+            config
+        };
+        for config in configs {
+            let rebuilt = ConfigBuilder::from(&config).build().unwrap();
+            assert_eq!(rebuilt, sorted(config));
+        }
+    }
+
+    #[test]
+    fn test_separator_sequence_empty() {
+        let sequence = |sequence: Option<&str>| {
+            ConfigBuilder::new()
+                .separator_type(Some("sequence".to_owned()))
+                .separator_sequence(sequence.map(str::to_owned))
+        };
+        assert!(sequence(None).build().is_err());
+        assert!(sequence(Some("")).build().is_err());
+        // emptied by the filters
+        assert!(
+            sequence(Some("|"))
+                .avoid_ambiguous(Some("true".to_owned()))
+                .build()
+                .is_err()
+        );
+        assert!(
+            sequence(Some("{}"))
+                .easy_typing(Some("true".to_owned()))
+                .build()
+                .is_err()
+        );
+        let config = sequence(Some("|-"))
+            .avoid_ambiguous(Some("true".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.separator_sequence, ['-']);
+    }
+
+    #[test]
+    fn test_avoid_ambiguous() {
+        let config = ConfigBuilder::new()
+            .avoid_ambiguous(Some("true".to_owned()))
+            .digit_class(Some("alphanumeric".to_owned()))
+            .padding_characters(Some("|!0".to_owned()))
+            .separator_characters(Some("`-".to_owned()))
+            .build()
+            .unwrap();
+        assert!(config.avoid_ambiguous);
+        assert_eq!(config.padding_characters, ['!']);
+        assert_eq!(config.separator_characters, ['-']);
+        assert_eq!(config.digit_characters.len(), 62 - 5);
+        assert!(!Config::default().avoid_ambiguous);
+        assert!(
+            ConfigBuilder::new()
+                .avoid_ambiguous(Some("yes".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_easy_typing() {
+        let make = |keyboard: &str| {
+            ConfigBuilder::new()
+                .easy_typing(Some("true".to_owned()))
+                .keyboard(Some(keyboard.to_owned()))
+                .digit_characters(Some("0123456789".to_owned()))
+                .padding_characters(Some("!-=#".to_owned()))
+                .separator_characters(Some("/.@".to_owned()))
+                .build()
+                .unwrap()
+        };
+        let config = make("qwerty");
+        assert!(config.easy_typing);
+        assert_eq!(config.padding_characters, ['-', '=']);
+        assert_eq!(config.separator_characters, ['.', '/']);
+        assert_eq!(config.digit_characters.len(), 10);
+        let config = make("mobile");
+        assert_eq!(config.keyboard, KeyboardLayout::Mobile);
+        assert_eq!(config.padding_characters, ['!', '-']);
+        assert_eq!(config.separator_characters, ['.', '/', '@']);
+        assert!(!Config::default().easy_typing);
+        assert_eq!(Config::default().keyboard, KeyboardLayout::Qwerty);
+        assert!(
+            ConfigBuilder::new()
+                .keyboard(Some("colemak".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_preset() {
+        let config = ConfigBuilder::new()
+            .preset(Some("Mobile".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.preset, Preset::Mobile);
+        assert_eq!(
+            config.word_transformation,
+            WordTransformationType::CapitalizeFirstWord
+        );
+        assert_eq!(config.keyboard, KeyboardLayout::Mobile);
+        assert!(config.easy_typing);
+        assert_eq!(config.digits_after, default::DIGITS_AFTER);
+        assert_eq!(
+            config.separator_characters,
+            ['!', '@', '$', '&', '-', ':', '?', '/', '.', ';']
+        );
+        // given settings win over the preset's
+        let config = ConfigBuilder::new()
+            .preset(Some("mobile".to_owned()))
+            .word_transformation(Some("lower".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(config.word_transformation, WordTransformationType::Lower);
+        assert_eq!(Config::default().preset, Preset::Desktop);
+        assert!(
+            ConfigBuilder::new()
+                .preset(Some("tv".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_excluded_words() {
+        let config = ConfigBuilder::new()
+            .excluded_words(Some("staple\nHorse  horse\r\n\n".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.excluded_words,
+            [Box::from("horse"), Box::from("staple")]
+        );
+        assert!(config.blocks_words());
+        assert_eq!(
+            config.blocked_words().collect::<Vec<&str>>(),
+            ["horse", "staple"]
+        );
+        assert!(!Config::default().blocks_words());
+    }
+
+    #[test]
+    fn test_joins_words() {
+        let make = |separators: &str, transformation: &str, joined_words: &str| {
+            ConfigBuilder::new()
+                .separator_characters(Some(separators.to_owned()))
+                .word_transformation(Some(transformation.to_owned()))
+                .joined_words(Some(joined_words.to_owned()))
+                .build()
+                .unwrap()
+        };
+        assert!(!Config::default().joins_words());
+        assert!(make("", "lower", "warn").joins_words());
+        assert!(make("", "random-upper-lower", "warn").joins_words());
+        assert!(!make("", "capitalize-first", "warn").joins_words());
+        assert!(!make("-", "lower", "warn").joins_words());
+        assert!(!make("", "lower", "warn").drops_prefixes());
+        assert!(make("", "lower", "prefix-free").drops_prefixes());
+        assert!(!make("-", "lower", "prefix-free").drops_prefixes());
+    }
+
+    #[test]
+    fn test_template() {
+        let config = ConfigBuilder::new()
+            .word_count(Some("2".to_owned()))
+            .template(Some(" Adj noun\tverb ".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.template,
+            [PartOfSpeech::Adj, PartOfSpeech::Noun, PartOfSpeech::Verb]
+        );
+        assert_eq!(config.word_count, 3);
+        assert!(Config::default().template.is_empty());
+        assert!(
+            ConfigBuilder::new()
+                .template(Some("adj pronoun".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_slot_wordlists() {
+        let default = builtin_wordlist_names()[0];
+        let config = ConfigBuilder::new()
+            .custom_wordlist(Some("team\nsquad\n\nteam\n".to_owned()))
+            .slot_wordlists(Some(format!("Custom {default}:3+adj  noun:1")))
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.custom_wordlist.as_ref(),
+            [Box::from("squad"), Box::from("team")]
+        );
+        assert_eq!(config.word_count, 3);
+        let source = |source, weight| SlotSource { source, weight };
+        assert_eq!(
+            config.slot_wordlists,
+            [
+                vec![source(WordSource::Custom, 1)],
+                vec![
+                    source(WordSource::Builtin(default), 3),
+                    source(WordSource::Part(PartOfSpeech::Adj), 1)
+                ],
+                vec![source(WordSource::Part(PartOfSpeech::Noun), 1)],
+            ]
+        );
+        assert_eq!(
+            slot_wordlists_text(&config.slot_wordlists),
+            format!("custom {default}:3+adj noun")
+        );
+        for invalid in ["custom", "adj:0", "adj:x", "adj+", "klingon"] {
+            assert!(
+                ConfigBuilder::new()
+                    .slot_wordlists(Some(invalid.to_owned()))
+                    .build()
+                    .is_err(),
+                "{invalid}"
+            );
+        }
+        assert!(
+            ConfigBuilder::new()
+                .template(Some("adj".to_owned()))
+                .slot_wordlists(Some("noun".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_wordlist_name() {
+        let default = builtin_wordlist_names()[0];
+        assert_eq!(Config::default().wordlist_name, default);
+        let config = ConfigBuilder::new()
+            .wordlist_name(Some(default.to_ascii_uppercase()))
+            .build()
+            .unwrap();
+        assert_eq!(config.wordlist_name, default);
+        assert!(
+            ConfigBuilder::new()
+                .wordlist_name(Some("klingon".to_owned()))
+                .build()
+                .is_err()
+        );
+    }
 }
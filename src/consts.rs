@@ -3,6 +3,9 @@
 /// 0-9
 pub const DIGIT_ALPHABET: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
 
+/// Characters which are easily mistaken for one another, such as `0` and `O`, or `1`, `l` and `I`
+pub const AMBIGUOUS_CHARACTERS: [char; 7] = ['0', 'O', '1', 'l', 'I', '|', '`'];
+
 #[expect(missing_docs, reason = "the names are clear enough")]
 /// Configuration defaults.
 pub mod default {
@@ -19,3 +22,25 @@ pub mod default {
     pub const PADDING_LENGTH_FIXED: u8 = 1;
     pub const PADDING_LENGTH_ADAPTIVE: u8 = 42;
 }
+
+/// Defaults of [`Preset::Mobile`], for typing on a phone.
+///
+/// Everything else keeps its [`default`] value, and the separator and padding alphabets
+/// are [`default::SYMBOL_ALPHABET`] cut down to the first symbol layer by easy typing.
+///
+/// [`Preset::Mobile`]: crate::types::Preset::Mobile
+pub mod mobile {
+    use crate::types::DigitPlacement;
+    use crate::types::KeyboardLayout;
+    use crate::types::WordTransformationType;
+
+    /// one capital, so that shift is pressed only once
+    pub const WORD_TRANSFORMATION: WordTransformationType =
+        WordTransformationType::CapitalizeFirstWord;
+    /// all of the digits in one group, so that the digit layer is switched to only once
+    pub const DIGIT_PLACEMENT: DigitPlacement = DigitPlacement::Ends;
+    /// a phone's on-screen keyboard
+    pub const KEYBOARD: KeyboardLayout = KeyboardLayout::Mobile;
+    /// leave out symbols beyond the first symbol layer, and words with any
+    pub const EASY_TYPING: bool = true;
+}
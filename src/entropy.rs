@@ -0,0 +1,801 @@
+//! Estimate the strength of the passwords a [`Config`] produces.
+//!
+//! Strength is measured in bits of entropy, assuming the attacker knows the config and the wordlist,
+//! which is the only safe assumption to make.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+
+use crate::config::Config;
+use crate::types::DigitPlacement;
+use crate::types::PaddingFill;
+use crate::types::PaddingType;
+use crate::types::Preset;
+use crate::types::SeparatorType;
+use crate::types::WordTransformationType;
+use crate::wordlist;
+
+/// log2 of `n` choices, which is 0 for a single choice or none at all.
+#[expect(clippy::cast_precision_loss, reason = "choices are nowhere near 2^52")]
+fn bits(n: usize) -> f64 {
+    if n <= 1 { 0.0 } else { (n as f64).log2() }
+}
+
+/// Round `words` down to a whole number of words, forgiving floating point error.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "word counts are small and never negative"
+)]
+fn whole_words(words: f64) -> usize {
+    (words + 1e-9).floor() as usize
+}
+
+/// A part of a password made of `count` choices from `choices` options.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Component {
+    /// number of options for each choice
+    pub choices: usize,
+    /// number of choices made
+    pub count: usize,
+    /// bits of entropy, if they differ from `count` equally likely choices from `choices` options
+    pub restricted_bits: Option<f64>,
+}
+
+impl Component {
+    /// Make `count` independent choices from `choices` options.
+    #[must_use]
+    pub const fn new(choices: usize, count: usize) -> Self {
+        Self {
+            choices,
+            count,
+            restricted_bits: None,
+        }
+    }
+    /// Combine the choices of `self` with those of `other`, which may have a different number of options.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        if other.count == 0 {
+            self
+        } else if self.count == 0 {
+            other
+        } else if self.choices == other.choices
+            && self.restricted_bits.is_none()
+            && other.restricted_bits.is_none()
+        {
+            Self::new(self.choices, self.count + other.count)
+        } else {
+            Self {
+                choices: self.choices.max(other.choices),
+                count: self.count + other.count,
+                restricted_bits: Some(self.bits() + other.bits()),
+            }
+        }
+    }
+    /// Bits of entropy from a single choice.
+    #[must_use]
+    pub fn bits_each(&self) -> f64 {
+        bits(self.choices)
+    }
+    /// Bits of entropy from all of the choices, as if they were all made from `choices` options.
+    #[must_use]
+    pub fn unrestricted_bits(&self) -> f64 {
+        #[expect(clippy::cast_precision_loss, reason = "at most 510 choices")]
+        let count = self.count as f64;
+        count * self.bits_each()
+    }
+    /// Bits of entropy from all of the choices.
+    #[must_use]
+    pub fn bits(&self) -> f64 {
+        self.restricted_bits
+            .unwrap_or_else(|| self.unrestricted_bits())
+    }
+}
+
+/// The choice of where to put `digit_group_count` groups of digits around `word_count` words.
+///
+/// Like the rest of the estimate, placements which are not equally likely are counted by the most likely one.
+fn placement(config: &Config, word_count: usize, digit_group_count: usize) -> Component {
+    match config.digit_placement {
+        DigitPlacement::RandomGap if word_count > 0 => {
+            let gaps = Component::new(word_count + 1, digit_group_count);
+            if digit_group_count == 2 && config.digits_before == config.digits_after {
+                // groups of the same length look alike, so swapping both their gaps and their digits
+                // gives the same password, which makes those in two different gaps twice as likely
+                Component {
+                    restricted_bits: Some(gaps.unrestricted_bits() - 1.0),
+                    ..gaps
+                }
+            } else {
+                gaps
+            }
+        }
+        DigitPlacement::Attached if word_count > 0 => Component::new(word_count, digit_group_count),
+        // there is only one place for the digits to go
+        _ => Component::new(1, 0),
+    }
+}
+
+/// Someone trying to guess a password.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Attacker {
+    /// who they are and how they guess
+    pub description: &'static str,
+    /// how many guesses they make per second
+    pub guesses_per_second: f64,
+}
+
+/// A few attacker models, from weakest to strongest.
+pub const ATTACKERS: [Attacker; 4] = [
+    Attacker {
+        description: "online, rate limited",
+        guesses_per_second: 10.0,
+    },
+    Attacker {
+        description: "offline, slow hash (bcrypt, scrypt, argon2)",
+        guesses_per_second: 1e5,
+    },
+    Attacker {
+        description: "offline, fast hash (MD5, SHA-1) on a GPU",
+        guesses_per_second: 1e10,
+    },
+    Attacker {
+        description: "offline, fast hash on a large cluster",
+        guesses_per_second: 1e14,
+    },
+];
+
+impl Attacker {
+    /// Average time in seconds for this attacker to guess a password with `bits` of entropy,
+    /// which is half of the time it takes to try every possibility.
+    #[must_use]
+    pub fn seconds_to_crack(&self, bits: f64) -> f64 {
+        (bits - 1.0).exp2() / self.guesses_per_second
+    }
+}
+
+/// Describe `seconds` in the largest sensible unit.
+fn format_duration(seconds: f64) -> String {
+    const UNITS: [(&str, f64); 6] = [
+        ("years", 365.25 * 24.0 * 60.0 * 60.0),
+        ("days", 24.0 * 60.0 * 60.0),
+        ("hours", 60.0 * 60.0),
+        ("minutes", 60.0),
+        ("seconds", 1.0),
+        ("milliseconds", 1e-3),
+    ];
+    let (unit, scale) = UNITS
+        .into_iter()
+        .find(|(_, scale)| seconds >= *scale)
+        .unwrap_or(UNITS[UNITS.len() - 1]);
+    let value = seconds / scale;
+    if value >= 1e6 {
+        format!("{value:.1e} {unit}")
+    } else {
+        format!("{value:.1} {unit}")
+    }
+}
+
+/// Bits of entropy contributed by each component of a password.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entropy {
+    /// the configured minimum word length
+    pub word_min_length: u8,
+    /// the configured maximum word length
+    pub word_max_length: u8,
+    /// the maximum password length, or 0 for none
+    pub max_length: u8,
+    /// the number of words each word slot may choose from
+    pub word_pool_sizes: Vec<usize>,
+    /// bits of entropy from choosing words, were there no maximum password length
+    pub unlimited_word_bits: f64,
+    /// choosing words from those which fit the length constraints,
+    /// and which fit together within the maximum password length
+    pub words: Component,
+    /// random word casing
+    pub casing: Component,
+    /// choosing digits
+    pub digits: Component,
+    /// choosing where the groups of digits go
+    pub placement: Component,
+    /// choosing the separator characters
+    pub separator: Component,
+    /// choosing the padding characters
+    pub padding: Component,
+    /// bits lost by avoiding ambiguous characters, see [`Self::with_ambiguity_baseline`]
+    pub ambiguity_cost: Option<f64>,
+    /// the preset used and the bits it loses against the desktop default, see [`Self::with_preset_baseline`]
+    pub preset_cost: Option<(Preset, f64)>,
+    /// bits lost as words run together and read as others, see [`Self::with_join_loss`]
+    pub join_loss: f64,
+}
+
+impl Entropy {
+    /// Estimate the entropy of passwords made with `config`,
+    /// given `length_counts`, the number of words of each length which fit its length constraints.
+    ///
+    /// Adaptive padding is only counted when it is always applied, so the estimate errs low.
+    /// Other policy rules are not accounted for, so the estimate errs high when they apply.
+    /// Neither is throwing away adjacent words which spell out a blocked word, which is rare enough not to matter.
+    /// Words running together are accounted for separately, see [`Self::with_join_loss`].
+    #[must_use]
+    pub fn new(config: &Config, length_counts: &BTreeMap<usize, usize>) -> Self {
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "wordlists are nowhere near 2^52 words"
+        )]
+        let length_counts = length_counts
+            .iter()
+            .map(|(length, words)| (*length, *words as f64))
+            .collect::<BTreeMap<usize, f64>>();
+        Self::for_slots(config, &vec![length_counts; config.word_count as usize])
+    }
+    /// Estimate the entropy of passwords made with `config`, given the number of words of each length
+    /// which fit its length constraints in the wordlists of each word slot, see [`slot_wordlists`].
+    /// A slot which mixes wordlists counts as fewer words when some are more likely than others,
+    /// see [`wordlist::mixed_length_counts`].
+    ///
+    /// The same caveats apply as for [`Self::new`].
+    ///
+    /// [`slot_wordlists`]: crate::password_maker::slot_wordlists
+    #[must_use]
+    pub fn for_slots(config: &Config, slot_length_counts: &[BTreeMap<usize, f64>]) -> Self {
+        let word_pool_sizes = slot_length_counts
+            .iter()
+            .map(|length_counts| whole_words(length_counts.values().sum()))
+            .collect::<Vec<usize>>();
+        let word_count = if word_pool_sizes.contains(&0) {
+            0
+        } else {
+            word_pool_sizes.len()
+        };
+        let digit_count = config.digit_count(word_count);
+        let part_count = config.part_count(word_count);
+        let digit_group_count = config.digit_group_count(word_count);
+        let placement = placement(config, word_count, digit_group_count);
+        // each word is either upper or lower case
+        let casing_choices =
+            if config.word_transformation == WordTransformationType::RandomUpperLower {
+                2
+            } else {
+                1
+            };
+        let max_unpadded_length = word_count * config.word_max_length as usize
+            + digit_count
+            + part_count.saturating_sub(1);
+        let padding_length = config.padding_length as usize;
+        let adaptive_length = padding_length.saturating_sub(max_unpadded_length);
+        // padding characters at each end, in the shortest password
+        let (front, back) = match config.padding_type {
+            PaddingType::None => (0, 0),
+            PaddingType::FixedFront => (padding_length, 0),
+            PaddingType::FixedBack => (0, padding_length),
+            PaddingType::FixedBoth | PaddingType::Fixed => (padding_length, padding_length),
+            PaddingType::AdaptiveFront => (adaptive_length, 0),
+            PaddingType::AdaptiveBack | PaddingType::Adaptive => (0, adaptive_length),
+        };
+        let [front_characters, back_characters] = config.padding_alphabets();
+        let padding = match config.padding_fill {
+            PaddingFill::Same => Component::new(
+                config.padding_characters.len(),
+                usize::from(front + back > 0),
+            ),
+            PaddingFill::Independent => {
+                Component::new(front_characters.len(), usize::from(front > 0))
+                    .and(Component::new(back_characters.len(), usize::from(back > 0)))
+            }
+            PaddingFill::Random => Component::new(front_characters.len(), front)
+                .and(Component::new(back_characters.len(), back)),
+        };
+        let words = if word_count == 0 {
+            Component::new(word_pool_sizes.iter().copied().min().unwrap_or_default(), 0)
+        } else {
+            word_pool_sizes
+                .iter()
+                .map(|size| Component::new(*size, 1))
+                .fold(Component::new(0, 0), Component::and)
+        };
+        let unlimited_word_bits = words.bits();
+        let word_bits = config.policy.word_length_budget(config).map(|budget| {
+            let fitting = if word_count == 0 {
+                1.0
+            } else {
+                wordlist::count_fitting_slots(slot_length_counts, budget)
+            };
+            if fitting <= 1.0 { 0.0 } else { fitting.log2() }
+        });
+        Self {
+            word_min_length: config.word_min_length,
+            word_max_length: config.word_max_length,
+            max_length: config.policy.max_length,
+            word_pool_sizes,
+            unlimited_word_bits,
+            words: Component {
+                restricted_bits: word_bits.or(words.restricted_bits),
+                ..words
+            },
+            casing: Component::new(casing_choices, word_count),
+            digits: Component::new(config.digit_characters.len(), digit_count),
+            placement,
+            separator: match config.separator_type {
+                SeparatorType::Single => Component::new(
+                    config.separator_characters.len(),
+                    usize::from(part_count > 1),
+                ),
+                SeparatorType::PerGap => Component::new(
+                    config.separator_characters.len(),
+                    part_count.saturating_sub(1),
+                ),
+                // the sequence is part of the config, so it adds nothing
+                SeparatorType::Sequence => Component::new(1, part_count.saturating_sub(1)),
+            },
+            padding,
+            ambiguity_cost: None,
+            preset_cost: None,
+            join_loss: 0.0,
+        }
+    }
+    /// Account for words which run together reading as others,
+    /// given `bits_per_pair`, the bits lost for each pair of adjacent words, see [`wordlist::join_loss`].
+    #[must_use]
+    #[expect(clippy::cast_precision_loss, reason = "word counts are tiny")]
+    pub fn with_join_loss(self, bits_per_pair: f64) -> Self {
+        Self {
+            join_loss: bits_per_pair * self.words.count.saturating_sub(1) as f64,
+            ..self
+        }
+    }
+    /// Record the bits lost by avoiding ambiguous characters,
+    /// given `baseline`, the estimate for the same config without avoiding them.
+    #[must_use]
+    pub fn with_ambiguity_baseline(self, baseline: &Self) -> Self {
+        Self {
+            ambiguity_cost: Some(baseline.total() - self.total()),
+            ..self
+        }
+    }
+    /// Record the bits lost by using `preset`,
+    /// given `baseline`, the estimate for the same config with the desktop defaults.
+    #[must_use]
+    pub fn with_preset_baseline(self, preset: Preset, baseline: &Self) -> Self {
+        Self {
+            preset_cost: Some((preset, baseline.total() - self.total())),
+            ..self
+        }
+    }
+    /// The number of words to choose from, or for each word slot if they differ.
+    fn word_pool_text(&self) -> String {
+        let sizes = &self.word_pool_sizes;
+        match sizes.split_last() {
+            Some((last, rest)) if sizes.iter().any(|size| size != last) => {
+                let rest = rest
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>();
+                format!("{} and {last}", rest.join(", "))
+            }
+            _ => self.words.choices.to_string(),
+        }
+    }
+    /// Each component along with its name.
+    #[must_use]
+    pub const fn components(&self) -> [(&'static str, Component); 6] {
+        [
+            ("words", self.words),
+            ("casing", self.casing),
+            ("digits", self.digits),
+            ("placement", self.placement),
+            ("separator", self.separator),
+            ("padding", self.padding),
+        ]
+    }
+    /// Total bits of entropy, less what is lost as words run together.
+    #[must_use]
+    pub fn total(&self) -> f64 {
+        self.components().iter().map(|(_, c)| c.bits()).sum::<f64>() - self.join_loss
+    }
+    /// Break down where the entropy comes from, and how long [`ATTACKERS`] would take to guess a password.
+    #[must_use]
+    pub fn explain(&self) -> String {
+        let mut result = format!(
+            "{:<10}{:>8}{:>7}{:>11}{:>9}\n",
+            "component", "choices", "count", "bits each", "bits"
+        );
+        for (name, component) in self.components() {
+            let _ = writeln!(
+                result,
+                "{name:<10}{:>8}{:>7}{:>11.2}{:>9.2}",
+                component.choices,
+                component.count,
+                component.bits_each(),
+                component.bits()
+            );
+        }
+        if self.join_loss > 0.0 {
+            let _ = writeln!(result, "{:<36}{:>9.2}", "joined", -self.join_loss);
+        }
+        let total = self.total();
+        let _ = writeln!(result, "{:<36}{total:>9.2}", "total");
+        let _ = writeln!(
+            result,
+            "\nword pool: {} words of length {} to {}",
+            self.word_pool_text(),
+            self.word_min_length,
+            self.word_max_length
+        );
+        if self.max_length > 0 {
+            let _ = writeln!(
+                result,
+                "max length {} leaves {:.2} of the {:.2} bits of word choices",
+                self.max_length,
+                self.words.bits(),
+                self.unlimited_word_bits
+            );
+        }
+        if let Some(cost) = self.ambiguity_cost {
+            let _ = writeln!(result, "avoiding ambiguous characters costs {cost:.2} bits");
+        }
+        if let Some((preset, cost)) = self.preset_cost {
+            let _ = writeln!(
+                result,
+                "the {preset} preset costs {cost:.2} bits against the desktop default"
+            );
+        }
+        let _ = writeln!(
+            result,
+            "assuming the attacker knows the config and the wordlist"
+        );
+        let _ = writeln!(
+            result,
+            "\n{:<45}{:>11}  average time to guess",
+            "attacker", "guesses/s"
+        );
+        for attacker in ATTACKERS {
+            let _ = writeln!(
+                result,
+                "{:<45}{:>11.0e}  {}",
+                attacker.description,
+                attacker.guesses_per_second,
+                format_duration(attacker.seconds_to_crack(total))
+            );
+        }
+        result
+    }
+}
+
+impl fmt::Display for Entropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "words     {:>7.2} bits ({} from {} words)",
+            self.words.bits(),
+            self.words.count,
+            self.word_pool_text()
+        )?;
+        writeln!(f, "casing    {:>7.2} bits", self.casing.bits())?;
+        writeln!(f, "digits    {:>7.2} bits", self.digits.bits())?;
+        writeln!(f, "placement {:>7.2} bits", self.placement.bits())?;
+        writeln!(f, "separator {:>7.2} bits", self.separator.bits())?;
+        writeln!(f, "padding   {:>7.2} bits", self.padding.bits())?;
+        if self.join_loss > 0.0 {
+            writeln!(f, "joined    {:>7.2} bits", -self.join_loss)?;
+        }
+        writeln!(f, "total     {:>7.2} bits", self.total())?;
+        if let Some(cost) = self.ambiguity_cost {
+            writeln!(f, "avoiding ambiguous characters costs {cost:.2} bits")?;
+        }
+        if let Some((preset, cost)) = self.preset_cost {
+            writeln!(
+                f,
+                "the {preset} preset costs {cost:.2} bits against the desktop default"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use std::collections::HashMap;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    /// A pool of `size` words of length 5.
+    fn pool(size: usize) -> BTreeMap<usize, usize> {
+        BTreeMap::from([(5, size)])
+    }
+
+    /// A pool of `size` words of length 5, for a word slot.
+    fn slot(size: f64) -> BTreeMap<usize, f64> {
+        BTreeMap::from([(5, size)])
+    }
+
+    #[test]
+    fn test_default() {
+        let entropy = Entropy::new(&Config::default(), &pool(7776));
+        assert_close(entropy.words.bits(), 4.0 * 7776_f64.log2());
+        assert_close(entropy.casing.bits(), 0.0);
+        assert_close(entropy.digits.bits(), 2.0 * 10_f64.log2());
+        assert_close(entropy.separator.bits(), 18_f64.log2());
+        assert_close(entropy.padding.bits(), 18_f64.log2());
+        assert_close(
+            entropy.total(),
+            entropy.words.bits()
+                + entropy.digits.bits()
+                + entropy.separator.bits()
+                + entropy.padding.bits(),
+        );
+    }
+
+    #[test]
+    fn test_preset_baseline() {
+        let config = ConfigBuilder::new()
+            .preset(Some("mobile".to_owned()))
+            .build()
+            .unwrap();
+        let entropy = Entropy::new(&config, &pool(7772));
+        // ten of the eighteen symbols are on the first symbol layer
+        assert_close(entropy.separator.bits(), 10_f64.log2());
+        let baseline = Entropy::new(&Config::default(), &pool(7776));
+        let entropy = entropy.with_preset_baseline(Preset::Mobile, &baseline);
+        let (preset, cost) = entropy.preset_cost.unwrap();
+        assert_eq!(preset, Preset::Mobile);
+        assert_close(cost, baseline.total() - entropy.total());
+        assert!(cost > 0.0);
+        assert!(
+            entropy
+                .to_string()
+                .contains("the mobile preset costs 1.70 bits against the desktop default"),
+            "{entropy}"
+        );
+    }
+
+    #[test]
+    fn test_empty_pool() {
+        let config = ConfigBuilder::new()
+            .word_transformation(Some("random-upper-lower".to_owned()))
+            .digits_after(Some("1".to_owned()))
+            .build()
+            .unwrap();
+        let entropy = Entropy::new(&config, &pool(0));
+        assert_eq!(entropy.words.count, 0);
+        assert_close(entropy.words.bits(), 0.0);
+        assert_close(entropy.casing.bits(), 0.0);
+        // a lone pseudo-word is never separated
+        assert_close(entropy.separator.bits(), 0.0);
+    }
+
+    #[test]
+    fn test_random_casing() {
+        let config = ConfigBuilder::new()
+            .word_transformation(Some("random-upper-lower".to_owned()))
+            .build()
+            .unwrap();
+        assert_close(Entropy::new(&config, &pool(2)).casing.bits(), 4.0);
+    }
+
+    #[test]
+    fn test_adaptive_padding() {
+        let make = |length: &str| {
+            ConfigBuilder::new()
+                .word_count(Some("1".to_owned()))
+                .word_max_length(Some("5".to_owned()))
+                .digits_after(Some("0".to_owned()))
+                .padding_type(Some("adaptive".to_owned()))
+                .padding_length(Some(length.to_owned()))
+                .build()
+                .unwrap()
+        };
+        assert_close(Entropy::new(&make("5"), &pool(10)).padding.bits(), 0.0);
+        assert_close(
+            Entropy::new(&make("6"), &pool(10)).padding.bits(),
+            18_f64.log2(),
+        );
+    }
+
+    #[test]
+    fn test_digit_class() {
+        let config = ConfigBuilder::new()
+            .digit_class(Some("hex".to_owned()))
+            .build()
+            .unwrap();
+        assert_close(Entropy::new(&config, &pool(10)).digits.bits(), 8.0);
+    }
+
+    #[test]
+    fn test_digit_placements() {
+        let make = |placement: &str| {
+            let config = ConfigBuilder::new()
+                .digits_before(Some("1".to_owned()))
+                .digit_placement(Some(placement.to_owned()))
+                .build()
+                .unwrap();
+            let entropy = Entropy::new(&config, &pool(10));
+            (entropy.digits.count, entropy.placement.bits())
+        };
+        assert_eq!(make("ends"), (3, 0.0));
+        // 2 groups, each in one of 5 gaps or attached to one of 4 words
+        assert_eq!(make("random-gap").0, 3);
+        assert_close(make("random-gap").1, 2.0 * 5_f64.log2());
+        assert_close(make("attached").1, 4.0);
+        assert_eq!(make("every-gap"), (9, 0.0));
+    }
+
+    #[test]
+    fn test_random_gap_alike_groups() {
+        // one digit from two in each group, in any of the 3 gaps around 2 words
+        let config = ConfigBuilder::new()
+            .word_count(Some("2".to_owned()))
+            .digits_before(Some("1".to_owned()))
+            .digits_after(Some("1".to_owned()))
+            .digit_characters(Some("ab".to_owned()))
+            .digit_placement(Some("random-gap".to_owned()))
+            .build()
+            .unwrap();
+        let entropy = Entropy::new(&config, &pool(10));
+        // every way of choosing a gap and a digit for each group, as the password maker does,
+        // with the before group ahead of the after group when they share a gap
+        let mut passwords = HashMap::<Vec<Vec<char>>, usize>::new();
+        for (before_gap, after_gap) in (0..3).flat_map(|i| (0..3).map(move |j| (i, j))) {
+            for (before, after) in ['a', 'b']
+                .into_iter()
+                .flat_map(|b| ['a', 'b'].map(|a| (b, a)))
+            {
+                let mut gaps = vec![Vec::new(); 3];
+                gaps[before_gap].push(before);
+                gaps[after_gap].push(after);
+                *passwords.entry(gaps).or_default() += 1;
+            }
+        }
+        // the groups only swap when they are in different gaps
+        assert_eq!(passwords.len(), 3 * 4 + 3 * 4);
+        let most_likely = passwords.values().copied().max().unwrap();
+        assert_eq!(most_likely, 2);
+        assert_close(
+            entropy.placement.bits() + entropy.digits.bits(),
+            (36.0 / 2.0_f64).log2(),
+        );
+    }
+
+    #[test]
+    fn test_padding_fills() {
+        let make = |fill: &str, padding_type: &str| {
+            let config = ConfigBuilder::new()
+                .padding_type(Some(padding_type.to_owned()))
+                .padding_length(Some("3".to_owned()))
+                .padding_fill(Some(fill.to_owned()))
+                .padding_front_characters(Some("ab".to_owned()))
+                .padding_back_characters(Some("abcd".to_owned()))
+                .build()
+                .unwrap();
+            Entropy::new(&config, &pool(10)).padding.bits()
+        };
+        assert_close(make("same", "fixed-both"), 18_f64.log2());
+        assert_close(make("independent", "fixed-both"), 3.0);
+        assert_close(make("independent", "fixed-back"), 2.0);
+        assert_close(make("random", "fixed-both"), 3.0 + 6.0);
+        assert_close(make("random", "fixed-front"), 3.0);
+        assert_close(make("random", "adaptive"), 0.0);
+    }
+
+    #[test]
+    fn test_component_and() {
+        let combined = Component::new(2, 3).and(Component::new(2, 1));
+        assert_eq!(combined, Component::new(2, 4));
+        let combined = Component::new(2, 3).and(Component::new(4, 1));
+        assert_eq!((combined.choices, combined.count), (4, 4));
+        assert_close(combined.bits(), 5.0);
+        assert_eq!(
+            Component::new(4, 0).and(Component::new(2, 1)),
+            Component::new(2, 1)
+        );
+    }
+
+    #[test]
+    fn test_separator_types() {
+        let make = |separator_type: &str| {
+            let config = ConfigBuilder::new()
+                .separator_type(Some(separator_type.to_owned()))
+                .separator_sequence(Some("-.".to_owned()))
+                .build()
+                .unwrap();
+            Entropy::new(&config, &pool(10)).separator.bits()
+        };
+        assert_close(make("single"), 18_f64.log2());
+        // 4 words and the digits leave 4 gaps
+        assert_close(make("per-gap"), 4.0 * 18_f64.log2());
+        assert_close(make("sequence"), 0.0);
+    }
+
+    #[test]
+    fn test_max_length() {
+        let config = ConfigBuilder::new()
+            .word_count(Some("2".to_owned()))
+            .digits_after(Some("0".to_owned()))
+            .padding_type(Some("none".to_owned()))
+            .max_length(Some("8".to_owned()))
+            .build()
+            .unwrap();
+        // 7 characters are left after the separator: 3+3, 3+4 or 4+3
+        let entropy = Entropy::new(&config, &BTreeMap::from([(3, 2), (4, 4), (5, 8)]));
+        assert_close(entropy.words.bits(), 20_f64.log2());
+        assert_close(entropy.words.unrestricted_bits(), 2.0 * 14_f64.log2());
+        assert!(
+            entropy
+                .explain()
+                .contains("max length 8 leaves 4.32 of the 7.61 bits")
+        );
+        let unlimited = Entropy::new(&Config::default(), &pool(10));
+        assert!(unlimited.words.restricted_bits.is_none());
+        assert!(!unlimited.explain().contains("max length"));
+    }
+
+    #[test]
+    fn test_for_slots() {
+        let config = ConfigBuilder::new()
+            .template(Some("adj noun verb".to_owned()))
+            .build()
+            .unwrap();
+        let entropy = Entropy::for_slots(&config, &[slot(2.0), slot(4.0), slot(8.0)]);
+        assert_eq!(entropy.words.count, 3);
+        assert_close(entropy.words.bits(), 6.0);
+        assert!(entropy.explain().contains("word pool: 2, 4 and 8 words"));
+        let same = Entropy::for_slots(&config, &[slot(4.0), slot(4.0), slot(4.0)]);
+        assert_close(
+            same.words.bits(),
+            Entropy::new(&config, &pool(4)).words.bits(),
+        );
+        assert!(same.explain().contains("word pool: 4 words"));
+        let empty = Entropy::for_slots(&config, &[slot(2.0), slot(0.0), slot(8.0)]);
+        assert_eq!(empty.words.count, 0);
+        assert_close(empty.words.bits(), 0.0);
+        // a mixed slot's fractional words are rounded down
+        let mixed = Entropy::for_slots(&config, &[slot(2.0), slot(4.999_999_999_99), slot(8.5)]);
+        assert!(mixed.explain().contains("word pool: 2, 5 and 8 words"));
+    }
+
+    #[test]
+    fn test_seconds_to_crack() {
+        let attacker = Attacker {
+            description: "",
+            guesses_per_second: 4.0,
+        };
+        // on average, half of the 2^10 possibilities are tried
+        assert_close(attacker.seconds_to_crack(10.0), 128.0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0.0), "0.0 milliseconds");
+        assert_eq!(format_duration(90.0), "1.5 minutes");
+        assert_eq!(format_duration(2.0 * 24.0 * 60.0 * 60.0), "2.0 days");
+        assert_eq!(format_duration(1e20), "3.2e12 years");
+    }
+
+    #[test]
+    fn test_join_loss() {
+        let entropy = Entropy::new(&Config::default(), &pool(7776));
+        let joined = entropy.clone().with_join_loss(0.5);
+        // four words have three gaps
+        assert_close(joined.join_loss, 1.5);
+        assert_close(joined.total(), entropy.total() - 1.5);
+        assert!(joined.to_string().contains("joined      -1.50 bits"));
+        assert!(!entropy.to_string().contains("joined"));
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = Entropy::new(&Config::default(), &pool(7776)).explain();
+        assert!(explanation.contains("word pool: 7776 words of length 3 to 11"));
+        for (name, _) in Entropy::new(&Config::default(), &pool(7776)).components() {
+            assert!(explanation.contains(name), "{name}");
+        }
+        for attacker in ATTACKERS {
+            assert!(explanation.contains(attacker.description));
+        }
+    }
+}
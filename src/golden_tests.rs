@@ -0,0 +1,183 @@
+//! Golden test vectors for seeded password generation.
+//!
+//! `tests/golden_vectors.txt` records which passwords a seed and a config produce.
+//! Any change to the order or number of random choices in [`PasswordMaker::make_password`]
+//! makes these tests fail, so that reproducible output only ever changes on purpose.
+//!
+//! To make such a change:
+//! 1. bump [`GENERATOR_VERSION`]
+//! 2. run `FMN_PASSGEN_BLESS=1 cargo test golden` to regenerate the expected passwords
+//!
+//! New vectors can be added without any `expect` lines and filled in the same way.
+//!
+//! [`PasswordMaker::make_password`]: crate::password_maker::PasswordMaker::make_password
+#![cfg(test)]
+use std::env;
+use std::fmt::Write;
+use std::fs;
+
+use crate::config::ConfigBuilder;
+use crate::password_maker::GENERATOR_VERSION;
+use crate::test_helpers::make_seeded_maker_big_list;
+use crate::types::PaddingType;
+use crate::types::StrEnum;
+use crate::types::WordTransformationType;
+
+const CORPUS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden_vectors.txt");
+const BLESS_VAR: &str = "FMN_PASSGEN_BLESS";
+
+/// One (seed, config) -> passwords record.
+#[derive(Debug, Default)]
+struct Vector {
+    seed: u64,
+    /// [`ConfigBuilder`] field names and values
+    settings: Vec<(String, String)>,
+    expected: Vec<String>,
+}
+
+impl Vector {
+    fn run(&self) -> Vec<String> {
+        let mut builder = ConfigBuilder::new();
+        for (name, value) in &self.settings {
+            assert!(
+                builder.set(name, Some(value.clone())),
+                "unknown config field `{name}`"
+            );
+        }
+        let mut maker = make_seeded_maker_big_list(self.seed);
+        maker.config = builder.build().unwrap();
+        maker
+            .make_passwords()
+            .unwrap()
+            .iter()
+            .map(|p| p.as_str().to_owned())
+            .collect()
+    }
+}
+
+/// Parse the corpus into its version and vectors.
+///
+/// ```text
+/// version 1
+///
+/// seed 1
+/// set padding_type fixed-both
+/// expect password
+/// ```
+///
+/// Values are the rest of the line, and may be empty.
+fn parse(corpus: &str) -> (u32, Vec<Vector>) {
+    let mut version = None;
+    let mut vectors: Vec<Vector> = Vec::new();
+    for line in corpus.lines() {
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match keyword {
+            "" => {}
+            "version" => version = Some(rest.parse().unwrap()),
+            "seed" => vectors.push(Vector {
+                seed: rest.parse().unwrap(),
+                ..Vector::default()
+            }),
+            "set" => {
+                let (name, value) = rest.split_once(' ').unwrap_or((rest, ""));
+                let vector = vectors.last_mut().expect("`set` before `seed`");
+                vector.settings.push((name.to_owned(), value.to_owned()));
+            }
+            "expect" => {
+                let vector = vectors.last_mut().expect("`expect` before `seed`");
+                vector.expected.push(rest.to_owned());
+            }
+            _ => panic!("unexpected line in golden vectors: `{line}`"),
+        }
+    }
+    (
+        version.expect("golden vectors must declare a version"),
+        vectors,
+    )
+}
+
+fn render(version: u32, vectors: &[Vector]) -> String {
+    let mut result = format!("version {version}\n");
+    for vector in vectors {
+        write!(result, "\nseed {}\n", vector.seed).unwrap();
+        for (name, value) in &vector.settings {
+            writeln!(result, "set {name} {value}").unwrap();
+        }
+        for password in &vector.expected {
+            writeln!(result, "expect {password}").unwrap();
+        }
+    }
+    result
+}
+
+#[test]
+fn test_golden_vectors() {
+    let corpus = fs::read_to_string(CORPUS_PATH).unwrap();
+    let (version, mut vectors) = parse(&corpus);
+    let mut changed = Vec::new();
+    for vector in &mut vectors {
+        let actual = vector.run();
+        if vector.expected != actual {
+            if !vector.expected.is_empty() {
+                changed.push(format!(
+                    "seed {} with {:?}: expected {:?}, got {actual:?}",
+                    vector.seed, vector.settings, vector.expected
+                ));
+            }
+            vector.expected = actual;
+        }
+    }
+    if env::var_os(BLESS_VAR).is_some() {
+        assert!(
+            changed.is_empty() || version != GENERATOR_VERSION,
+            "seeded output changed, bump GENERATOR_VERSION before regenerating:\n{}",
+            changed.join("\n")
+        );
+        fs::write(CORPUS_PATH, render(GENERATOR_VERSION, &vectors)).unwrap();
+        return;
+    }
+    assert!(
+        changed.is_empty(),
+        "seeded output changed. If this is deliberate, bump GENERATOR_VERSION and rerun with {BLESS_VAR}=1:\n{}",
+        changed.join("\n")
+    );
+    assert_eq!(
+        version, GENERATOR_VERSION,
+        "GENERATOR_VERSION changed, rerun with {BLESS_VAR}=1 to update the golden vectors"
+    );
+    assert!(
+        vectors.iter().all(|v| !v.expected.is_empty()),
+        "some golden vectors have no expected passwords, rerun with {BLESS_VAR}=1"
+    );
+}
+
+/// The corpus should exercise every [`StrEnum`] option that influences the output.
+#[test]
+fn test_golden_vectors_coverage() {
+    let corpus = fs::read_to_string(CORPUS_PATH).unwrap();
+    let (_, vectors) = parse(&corpus);
+    let used = |field: &str, value: &str| {
+        vectors
+            .iter()
+            .any(|v| v.settings.iter().any(|(n, v)| n == field && v == value))
+    };
+    for (name, _) in WordTransformationType::NAME_MEMBER_ARR {
+        assert!(used("word_transformation", name), "{name} is not covered");
+    }
+    for (name, _) in PaddingType::NAME_MEMBER_ARR {
+        assert!(used("padding_type", name), "{name} is not covered");
+    }
+}
+
+#[test]
+fn test_render_round_trip() {
+    let corpus =
+        "version 3\n\nseed 7\nset separator_characters \nset count 2\nexpect a b\nexpect c\n";
+    let (version, vectors) = parse(corpus);
+    assert_eq!(version, 3);
+    assert_eq!(
+        vectors[0].settings[0],
+        ("separator_characters".to_owned(), String::new())
+    );
+    assert_eq!(render(version, &vectors), corpus);
+}
@@ -1,9 +1,5 @@
 //! A GUI interface for fmn-passgen
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-// provides:
-// static WORDLIST: &[&str] = &[...]
-include!(concat!(env!("OUT_DIR"), "/wordlist.rs"));
-
 use std::sync::Arc;
 
 use eframe::egui;
@@ -18,26 +14,79 @@ use rand::rngs::SmallRng;
 use fmn_passgen::config::Config;
 use fmn_passgen::config::ConfigBuilder;
 use fmn_passgen::password_maker::PasswordMaker;
+use fmn_passgen::password_maker::builtin_wordlist_names;
+use fmn_passgen::password_maker::wordlist_for;
+use fmn_passgen::policy::PolicyError;
+use fmn_passgen::secret::SecretString;
+use fmn_passgen::types::DigitClass;
+use fmn_passgen::types::DigitPlacement;
+use fmn_passgen::types::JoinedWords;
+use fmn_passgen::types::KeyboardLayout;
+use fmn_passgen::types::PaddingFill;
 use fmn_passgen::types::PaddingType;
+use fmn_passgen::types::PartOfSpeech;
+use fmn_passgen::types::Preset;
+use fmn_passgen::types::SeparatorType;
 use fmn_passgen::types::StrEnum;
 use fmn_passgen::types::WordTransformationType;
 
-const INITIAL_SEED: u64 = 13414357264162109690;
+const INITIAL_SEED: u64 = 13_414_357_264_162_109_690;
 const MIN_WIDTH: f32 = 400.0;
 const MIN_HEIGHT: f32 = 740.0;
 const BUTTON_FULL_WIDTH: f32 = 384.0;
 const DEFAULT_SPACING: f32 = 17.0;
 const COMPACT_SPACING: f32 = 3.0;
 
+/// A drop down to choose one of the `shown` members of `T`, returning whether the choice changed.
+fn enum_combo_box<T: StrEnum + PartialEq>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut T,
+    shown: impl Fn(T) -> bool,
+) -> bool {
+    let previous = *value;
+    egui::ComboBox::from_label(label)
+        .selected_text(value.to_static_str())
+        .show_ui(ui, |ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(COMPACT_SPACING, COMPACT_SPACING);
+            for (description, item) in T::NAME_MEMBER_ARR {
+                if shown(*item) {
+                    ui.selectable_value(value, *item, *description);
+                }
+            }
+        });
+    *value != previous
+}
+
+/// A labelled single line text field.
+fn text_field(ui: &mut egui::Ui, label: &str, text: &mut String) {
+    ui.horizontal(|ui| {
+        let name_label = ui.label(label);
+        ui.text_edit_singleline(text).labelled_by(name_label.id);
+    });
+}
+
 struct App {
+    /// the settings as the widgets show them, with the character lists kept in the text fields below
+    settings: Config,
     config_prev: Config,
+    /// the settings and character lists as [`ConfigBuilder::build`] validated and filtered them
     config_curr: Config,
+    /// why the settings could not be built into `config_curr`
+    config_error: Option<String>,
     password_maker: PasswordMaker<OsRng>,
     preview_maker: PasswordMaker<SmallRng>,
-    preview: String,
-    generated_passwords: Vec<String>,
+    preview: Result<SecretString, PolicyError>,
+    generated_passwords: Result<Vec<SecretString>, PolicyError>,
+    /// why the selected preset could not be applied
+    preset_error: Option<String>,
+    digit_characters: String,
     padding_characters: String,
+    padding_front_characters: String,
+    padding_back_characters: String,
     separator_characters: String,
+    separator_sequence: String,
+    template: String,
     seed_string: String,
     curr_seed: u64,
     prev_seed: u64,
@@ -49,25 +98,30 @@ impl App {
             style.spacing.item_spacing = egui::vec2(DEFAULT_SPACING, DEFAULT_SPACING);
             style.interaction.selectable_labels = false;
         });
-        let config_prev = ConfigBuilder::new().build().unwrap();
+        let config_prev = Config::default();
         let config_curr = config_prev.clone();
         // TODO take references to a config?
         let password_maker = PasswordMaker::<OsRng>::new(config_curr.clone());
         let seed_string = INITIAL_SEED.to_string();
         let curr_seed = INITIAL_SEED;
         let prev_seed = INITIAL_SEED;
-        let mut preview_maker: PasswordMaker<SmallRng> = PasswordMaker {
-            rng: SmallRng::seed_from_u64(curr_seed).unwrap_err(),
-            config: config_curr.clone(),
-            wordlist: WORDLIST.iter().map(|s| String::from(*s)).collect(),
-        };
-        let preview: String = preview_maker.make_password();
-        let generated_passwords: Vec<String> = Vec::new();
+        let mut preview_maker =
+            PasswordMaker::with_rng(SmallRng::seed_from_u64(curr_seed), config_curr.clone());
+        let preview = preview_maker.make_password();
+        let generated_passwords = Ok(Vec::new());
+        let digit_characters: String = config_curr.digit_characters.iter().collect();
         let padding_characters: String = config_curr.padding_characters.iter().collect();
+        let padding_front_characters: String =
+            config_curr.padding_front_characters.iter().collect();
+        let padding_back_characters: String = config_curr.padding_back_characters.iter().collect();
         let separator_characters: String = config_curr.separator_characters.iter().collect();
+        let separator_sequence: String = config_curr.separator_sequence.iter().collect();
+        let template = String::new();
         Self {
+            settings: config_curr.clone(),
             config_prev,
             config_curr,
+            config_error: None,
             password_maker,
             seed_string,
             curr_seed,
@@ -75,154 +129,277 @@ impl App {
             preview_maker,
             preview,
             generated_passwords,
+            preset_error: None,
+            digit_characters,
             padding_characters,
+            padding_front_characters,
+            padding_back_characters,
             separator_characters,
+            separator_sequence,
+            template,
         }
     }
-    fn show_inner(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        egui::CollapsingHeader::new("preview")
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    let response = ui.add_sized(
-                        [160.0, DEFAULT_SPACING],
-                        egui::TextEdit::singleline(&mut self.seed_string),
-                    );
-                    if response.changed() {
-                        if let Ok(seed) = self.seed_string.parse::<u64>() {
-                            self.curr_seed = seed;
-                        } else {
-                            self.seed_string = self.curr_seed.to_string();
-                        }
-                    }
-                    if ui.button("random seed").clicked() {
-                        self.curr_seed = self.password_maker.rng.random();
-                        self.seed_string = self.curr_seed.to_string();
-                    }
-                });
-                if self.config_curr != self.config_prev || self.curr_seed != self.prev_seed {
-                    self.preview_maker.rng = SmallRng::seed_from_u64(self.curr_seed).unwrap_err();
-                    self.preview_maker.config = self.config_curr.clone();
-                    self.preview = self.preview_maker.make_password();
-                    self.config_prev = self.config_curr.clone();
-                    self.prev_seed = self.curr_seed;
+    /// Start over from the defaults of the selected preset, keeping the count,
+    /// or go back to the `previous` one if it can not be applied.
+    fn apply_preset(&mut self, previous: Preset) {
+        let count = self.settings.count;
+        match ConfigBuilder::new()
+            .preset(Some(self.settings.preset.to_static_str().to_owned()))
+            .build()
+        {
+            Ok(config) => {
+                self.settings = config;
+                self.preset_error = None;
+            }
+            Err(e) => {
+                self.settings.preset = previous;
+                self.preset_error = Some(e.to_string());
+                return;
+            }
+        }
+        self.settings.count = count;
+        self.digit_characters = self.settings.digit_characters.iter().collect();
+        self.padding_characters = self.settings.padding_characters.iter().collect();
+        self.padding_front_characters = self.settings.padding_front_characters.iter().collect();
+        self.padding_back_characters = self.settings.padding_back_characters.iter().collect();
+        self.separator_characters = self.settings.separator_characters.iter().collect();
+        self.separator_sequence = self.settings.separator_sequence.iter().collect();
+        self.template.clear();
+    }
+    /// Build the settings and the character lists in the text fields into `config_curr`.
+    ///
+    /// The character lists are filtered anew every time,
+    /// so that unticking a filter brings back what it took out.
+    fn build_config(&mut self) {
+        match ConfigBuilder::from(&self.settings)
+            .digit_characters(Some(self.digit_characters.clone()))
+            .padding_characters(Some(self.padding_characters.clone()))
+            .padding_front_characters(Some(self.padding_front_characters.clone()))
+            .padding_back_characters(Some(self.padding_back_characters.clone()))
+            .separator_characters(Some(self.separator_characters.clone()))
+            .separator_sequence(Some(self.separator_sequence.clone()))
+            .build()
+        {
+            Ok(config) => {
+                self.config_curr = config;
+                self.config_error = None;
+            }
+            Err(e) => self.config_error = Some(e.to_string()),
+        }
+    }
+    fn show_preview(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let response = ui.add_sized(
+                [160.0, DEFAULT_SPACING],
+                egui::TextEdit::singleline(&mut self.seed_string),
+            );
+            if response.changed() {
+                if let Ok(seed) = self.seed_string.parse::<u64>() {
+                    self.curr_seed = seed;
+                } else {
+                    self.seed_string = self.curr_seed.to_string();
+                }
+            }
+            if ui.button("random seed").clicked() {
+                self.curr_seed = self.password_maker.rng.random();
+                self.seed_string = self.curr_seed.to_string();
+            }
+        });
+        if self.config_curr != self.config_prev || self.curr_seed != self.prev_seed {
+            self.preview_maker.rng = SmallRng::seed_from_u64(self.curr_seed).unwrap_err();
+            self.preview_maker.wordlist = wordlist_for(&self.config_curr);
+            self.preview_maker.config = self.config_curr.clone();
+            self.preview = self.preview_maker.make_password();
+            self.config_prev = self.config_curr.clone();
+            self.prev_seed = self.curr_seed;
+        }
+        if let Some(e) = &self.config_error {
+            ui.label(RichText::new(e).color(Color32::RED));
+            return;
+        }
+        match &self.preview {
+            Ok(preview) => {
+                ui.label(RichText::new(preview.as_str()).color(Color32::ORANGE));
+            }
+            Err(e) => {
+                ui.label(RichText::new(e.to_string()).color(Color32::RED));
+            }
+        }
+    }
+    fn show_preset(&mut self, ui: &mut egui::Ui) {
+        let preset = self.settings.preset;
+        if enum_combo_box(ui, "preset", &mut self.settings.preset, |_| true) {
+            self.apply_preset(preset);
+        }
+        if let Some(e) = &self.preset_error {
+            ui.label(RichText::new(e).color(Color32::RED));
+        }
+    }
+    fn show_words(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("wordlist")
+            .selected_text(self.settings.wordlist_name)
+            .show_ui(ui, |ui| {
+                ui.style_mut().spacing.item_spacing = egui::vec2(COMPACT_SPACING, COMPACT_SPACING);
+                for name in builtin_wordlist_names() {
+                    ui.selectable_value(&mut self.settings.wordlist_name, name, *name);
                 }
-                ui.label(RichText::new(&self.preview).color(Color32::ORANGE));
-            });
-        egui::CollapsingHeader::new("words")
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.add(
-                    egui::Slider::new(&mut self.config_curr.word_count, 0..=32)
-                        .text("count")
-                        .logarithmic(true),
-                );
-                ui.add(
-                    egui::Slider::new(&mut self.config_curr.word_min_length, 3..=9)
-                        .text("min length"),
-                );
-                ui.add(
-                    egui::Slider::new(
-                        &mut self.config_curr.word_max_length,
-                        self.config_curr.word_min_length..=9,
-                    )
-                    .text("max length"),
-                );
-                egui::ComboBox::from_label("transform")
-                    .selected_text(self.config_curr.word_transformation.to_static_str())
-                    .show_ui(ui, |ui| {
-                        ui.style_mut().spacing.item_spacing =
-                            egui::vec2(COMPACT_SPACING, COMPACT_SPACING);
-                        for (description, item) in WordTransformationType::NAME_MEMBER_ARR {
-                            ui.selectable_value(
-                                &mut self.config_curr.word_transformation,
-                                *item,
-                                *description,
-                            );
-                        }
-                    });
-            });
-        egui::CollapsingHeader::new("digits")
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.add(
-                    egui::Slider::new(&mut self.config_curr.digits_before, 0..=255)
-                        .text("before")
-                        .logarithmic(true),
-                );
-                ui.add(
-                    egui::Slider::new(&mut self.config_curr.digits_after, 0..=255)
-                        .text("after")
-                        .logarithmic(true),
-                );
-            });
-        egui::CollapsingHeader::new("padding")
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    let name_label = ui.label("possible choices");
-                    ui.text_edit_singleline(&mut self.padding_characters)
-                        .labelled_by(name_label.id);
-                });
-                self.config_curr.padding_characters = self.padding_characters.chars().collect();
-                egui::ComboBox::from_label("type")
-                    .selected_text(self.config_curr.padding_type.to_static_str())
-                    .show_ui(ui, |ui| {
-                        ui.style_mut().spacing.item_spacing =
-                            egui::vec2(COMPACT_SPACING, COMPACT_SPACING);
-                        for (description, item) in PaddingType::NAME_MEMBER_ARR {
-                            // displaying aliases is unnecessary
-                            if matches!(item, PaddingType::Fixed | PaddingType::Adaptive) {
-                                continue;
-                            }
-                            ui.selectable_value(
-                                &mut self.config_curr.padding_type,
-                                *item,
-                                *description,
-                            );
-                        }
-                    });
-                ui.add(
-                    egui::Slider::new(&mut self.config_curr.padding_length, 0..=255)
-                        .text("length")
-                        .logarithmic(true),
-                );
-            });
-        egui::CollapsingHeader::new("separator")
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    let name_label = ui.label("possible choices");
-                    ui.text_edit_singleline(&mut self.separator_characters)
-                        .labelled_by(name_label.id);
-                });
-                self.config_curr.separator_characters = self.separator_characters.chars().collect();
             });
+        text_field(ui, "template", &mut self.template);
+        // parts of speech which are not (yet) spelled out in full are left out
+        self.settings.template = self
+            .template
+            .split_whitespace()
+            .filter_map(|part| {
+                PartOfSpeech::to_member(&part.to_ascii_lowercase())
+                    .ok()
+                    .copied()
+            })
+            .take(32)
+            .collect();
+        if self.settings.template.is_empty() {
+            ui.add(
+                egui::Slider::new(&mut self.settings.word_count, 0..=32)
+                    .text("count")
+                    .logarithmic(true),
+            );
+        }
+        ui.add(egui::Slider::new(&mut self.settings.word_min_length, 3..=9).text("min length"));
         ui.add(
-            egui::Slider::new(&mut self.config_curr.count, 1..=255)
+            egui::Slider::new(
+                &mut self.settings.word_max_length,
+                self.settings.word_min_length..=9,
+            )
+            .text("max length"),
+        );
+        enum_combo_box::<WordTransformationType>(
+            ui,
+            "transform",
+            &mut self.settings.word_transformation,
+            |_| true,
+        );
+    }
+    fn show_digits(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::Slider::new(&mut self.settings.digits_before, 0..=255)
+                .text("before")
+                .logarithmic(true),
+        );
+        ui.add(
+            egui::Slider::new(&mut self.settings.digits_after, 0..=255)
+                .text("after")
+                .logarithmic(true),
+        );
+        enum_combo_box::<DigitPlacement>(
+            ui,
+            "placement",
+            &mut self.settings.digit_placement,
+            |_| true,
+        );
+        if enum_combo_box::<DigitClass>(ui, "class", &mut self.settings.digit_class, |_| true) {
+            self.digit_characters = self.settings.digit_class.characters().into_iter().collect();
+        }
+        text_field(ui, "possible choices", &mut self.digit_characters);
+    }
+    fn show_padding(&mut self, ui: &mut egui::Ui) {
+        text_field(ui, "possible choices", &mut self.padding_characters);
+        // displaying aliases is unnecessary
+        enum_combo_box(ui, "type", &mut self.settings.padding_type, |item| {
+            !matches!(item, PaddingType::Fixed | PaddingType::Adaptive)
+        });
+        ui.add(
+            egui::Slider::new(&mut self.settings.padding_length, 0..=255)
+                .text("length")
+                .logarithmic(true),
+        );
+        enum_combo_box::<PaddingFill>(ui, "fill", &mut self.settings.padding_fill, |_| true);
+        if self.settings.padding_fill != PaddingFill::Same {
+            text_field(ui, "front choices", &mut self.padding_front_characters);
+            text_field(ui, "back choices", &mut self.padding_back_characters);
+        }
+    }
+    fn show_separator(&mut self, ui: &mut egui::Ui) {
+        text_field(ui, "possible choices", &mut self.separator_characters);
+        enum_combo_box::<SeparatorType>(
+            ui,
+            "separator type",
+            &mut self.settings.separator_type,
+            |_| true,
+        );
+        if self.settings.separator_type == SeparatorType::Sequence {
+            text_field(ui, "sequence", &mut self.separator_sequence);
+        }
+        enum_combo_box::<JoinedWords>(ui, "joined words", &mut self.settings.joined_words, |_| {
+            true
+        });
+    }
+    fn show_filters(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(
+            &mut self.settings.avoid_ambiguous,
+            "avoid ambiguous characters",
+        );
+        enum_combo_box::<KeyboardLayout>(ui, "keyboard", &mut self.settings.keyboard, |_| true);
+        ui.checkbox(
+            &mut self.settings.easy_typing,
+            "only easily typed symbols and words",
+        );
+        ui.checkbox(&mut self.settings.blocklist, "leave out offensive words");
+    }
+    fn show_generated(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.add(
+            egui::Slider::new(&mut self.settings.count, 1..=255)
                 .text("how many to generate")
                 .logarithmic(true),
         );
 
-        if ui
-            .add_sized(
+        // there is nothing to generate with until the settings build
+        let generate = ui.add_enabled_ui(self.config_error.is_none(), |ui| {
+            ui.add_sized(
                 [BUTTON_FULL_WIDTH, DEFAULT_SPACING],
                 egui::Button::new("generate").fill(Color32::DARK_GREEN),
             )
-            .clicked()
-        {
+        });
+        if generate.inner.clicked() {
+            self.password_maker.wordlist = wordlist_for(&self.config_curr);
             self.password_maker.config = self.config_curr.clone();
             self.generated_passwords = self.password_maker.make_passwords();
         }
 
-        for item in self.generated_passwords.iter() {
-            ui.horizontal(|ui| {
-                if ui.button("📋").clicked() {
-                    ctx.copy_text(item.clone());
+        match &self.generated_passwords {
+            Ok(passwords) => {
+                for item in passwords {
+                    ui.horizontal(|ui| {
+                        if ui.button("📋").clicked() {
+                            ctx.copy_text(item.to_string());
+                        }
+                        ui.add(egui::Label::new(item.as_str()).selectable(true).wrap());
+                    });
                 }
-                ui.add(egui::Label::new(item).selectable(true).wrap());
-            });
+            }
+            Err(e) => {
+                ui.label(RichText::new(e.to_string()).color(Color32::RED));
+            }
         }
+    }
+    fn show_inner(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        egui::CollapsingHeader::new("preview")
+            .default_open(true)
+            .show(ui, |ui| self.show_preview(ui));
+        self.show_preset(ui);
+        egui::CollapsingHeader::new("words")
+            .default_open(true)
+            .show(ui, |ui| self.show_words(ui));
+        egui::CollapsingHeader::new("digits")
+            .default_open(true)
+            .show(ui, |ui| self.show_digits(ui));
+        egui::CollapsingHeader::new("padding")
+            .default_open(true)
+            .show(ui, |ui| self.show_padding(ui));
+        egui::CollapsingHeader::new("separator")
+            .default_open(true)
+            .show(ui, |ui| self.show_separator(ui));
+        self.show_filters(ui);
+        self.build_config();
+        self.show_generated(ui, ctx);
         ui.allocate_space(ui.available_size());
     }
 }
@@ -236,7 +413,8 @@ impl eframe::App for App {
 }
 
 fn main() -> eframe::Result {
-    let icon = eframe::icon_data::from_png_bytes(include_bytes!("../../icon.png")).unwrap();
+    let icon = eframe::icon_data::from_png_bytes(include_bytes!("../../icon.png"))
+        .expect("the icon built into the binary is a valid png");
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_min_inner_size([MIN_WIDTH, MIN_HEIGHT])
@@ -0,0 +1,350 @@
+//! Estimate how costly passwords are to type on a [`KeyboardLayout`].
+//!
+//! Each layout is a few rows of keys, giving the character each key types on its own,
+//! with shift held, and with Alt Gr held. Mobile keyboards have no Alt Gr,
+//! but their digits and symbols are on separate layers which must be switched to.
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::types::KeyboardLayout;
+use crate::types::StrEnum;
+
+/// The hand, or on a phone the thumb, which types a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    /// the left hand
+    Left,
+    /// the right hand
+    Right,
+}
+
+/// Where a character is on a keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    /// the layer the key is on, 0 for letters, only mobile keyboards have more than one
+    pub layer: u8,
+    /// whether shift or Alt Gr is needed as well
+    pub modified: bool,
+    /// which hand types the key, or [`None`] for the space bar
+    pub hand: Option<Hand>,
+}
+
+/// A row of keys, with a space for a key which types nothing at that level.
+struct Row {
+    layer: u8,
+    /// the characters typed on their own, with shift and with Alt Gr
+    levels: [&'static str; 3],
+    /// how many keys from the start of the row are typed with the left hand
+    left: usize,
+}
+
+/// Shorthand for a [`Row`].
+const fn row(
+    layer: u8,
+    plain: &'static str,
+    shifted: &'static str,
+    alt: &'static str,
+    left: usize,
+) -> Row {
+    Row {
+        layer,
+        levels: [plain, shifted, alt],
+        left,
+    }
+}
+
+/// US QWERTY.
+const QWERTY: &[Row] = &[
+    row(0, "`1234567890-=", "~!@#$%^&*()_+", "", 6),
+    row(0, "qwertyuiop[]\\", "QWERTYUIOP{}|", "", 5),
+    row(0, "asdfghjkl;'", "ASDFGHJKL:\"", "", 5),
+    row(0, "zxcvbnm,./", "ZXCVBNM<>?", "", 5),
+];
+
+/// French AZERTY, where the digits need shift.
+const AZERTY: &[Row] = &[
+    row(0, "²&é\"'(-è_çà)=", " 1234567890°+", "  ~#{[|`\\^@]}", 6),
+    row(0, "azertyuiop^$", "AZERTYUIOP¨£", "  €        ¤", 5),
+    row(0, "qsdfghjklmù*", "QSDFGHJKLM%µ", "", 5),
+    row(0, "<wxcvbn,;:!", ">WXCVBN?./§", "", 6),
+];
+
+/// German QWERTZ.
+const QWERTZ: &[Row] = &[
+    row(0, "^1234567890ß´", "°!\"§$%&/()=?`", "  ²³    {[]}\\", 6),
+    row(0, "qwertzuiopü+", "QWERTZUIOPÜ*", "@ €        ~", 5),
+    row(0, "asdfghjklöä#", "ASDFGHJKLÖÄ'", "", 5),
+    row(0, "<yxcvbnm,.-", ">YXCVBNM;:_", "|      µ", 6),
+];
+
+/// US Dvorak.
+const DVORAK: &[Row] = &[
+    row(0, "`1234567890[]", "~!@#$%^&*(){}", "", 6),
+    row(0, "',.pyfgcrl/=\\", "\"<>PYFGCRL?+|", "", 5),
+    row(0, "aoeuidhtns-", "AOEUIDHTNS_", "", 5),
+    row(0, ";qjkxbmwvz", ":QJKXBMWVZ", "", 5),
+];
+
+/// A phone's on-screen keyboard, with letters, then digits and common symbols, then the rest.
+const MOBILE: &[Row] = &[
+    row(0, "qwertyuiop", "QWERTYUIOP", "", 5),
+    row(0, "asdfghjkl", "ASDFGHJKL", "", 5),
+    row(0, "zxcvbnm", "ZXCVBNM", "", 4),
+    row(1, "1234567890", "", "", 5),
+    row(1, "-/:;()$&@\"", "", "", 5),
+    row(1, ".,?!'", "", "", 3),
+    row(2, "[]{}#%^*+=", "", "", 5),
+    row(2, "_\\|~<>€£¥•", "", "", 5),
+];
+
+/// The keys of each [`KeyboardLayout`], in declaration order, see [`key`].
+static KEYS: LazyLock<Vec<HashMap<char, Key>>> = LazyLock::new(|| {
+    KeyboardLayout::NAME_MEMBER_ARR
+        .iter()
+        .map(|(_, layout)| {
+            let mut keys = HashMap::from([(
+                ' ',
+                Key {
+                    layer: 0,
+                    modified: false,
+                    hand: None,
+                },
+            )]);
+            for row in rows(*layout) {
+                for (level, characters) in row.levels.iter().enumerate() {
+                    for (i, c) in characters.chars().enumerate() {
+                        if c == ' ' {
+                            continue;
+                        }
+                        // a character on more than one key is typed with the first
+                        keys.entry(c).or_insert(Key {
+                            layer: row.layer,
+                            modified: level > 0,
+                            hand: Some(if i < row.left {
+                                Hand::Left
+                            } else {
+                                Hand::Right
+                            }),
+                        });
+                    }
+                }
+            }
+            keys
+        })
+        .collect()
+});
+
+const fn rows(layout: KeyboardLayout) -> &'static [Row] {
+    match layout {
+        KeyboardLayout::Qwerty => QWERTY,
+        KeyboardLayout::Azerty => AZERTY,
+        KeyboardLayout::Qwertz => QWERTZ,
+        KeyboardLayout::Dvorak => DVORAK,
+        KeyboardLayout::Mobile => MOBILE,
+    }
+}
+
+/// Where `c` is on `layout`, or [`None`] if it can not be typed directly.
+#[must_use]
+pub fn key(c: char, layout: KeyboardLayout) -> Option<Key> {
+    KEYS[layout as usize].get(&c).copied()
+}
+
+/// Whether `c` is easy to type on `layout`, needing neither shift nor Alt Gr,
+/// and on a phone being at most one layer switch away from the letters.
+#[must_use]
+pub fn is_easy(c: char, layout: KeyboardLayout) -> bool {
+    key(c, layout).is_some_and(|key| !key.modified && key.layer <= 1)
+}
+
+/// Whether every character of `word` in lowercase is typed on `layout` without shift, Alt Gr
+/// or switching away from the letters.
+#[must_use]
+pub fn is_easy_word(word: &str, layout: KeyboardLayout) -> bool {
+    word.to_lowercase()
+        .chars()
+        .all(|c| key(c, layout).is_some_and(|key| !key.modified && key.layer == 0))
+}
+
+/// How costly a password is to type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TypingCost {
+    /// key presses in all, counting shift, Alt Gr and layer switches
+    pub keystrokes: usize,
+    /// presses of shift or Alt Gr
+    pub shifts: usize,
+    /// presses of the keys which switch between a phone keyboard's layers
+    pub layer_switches: usize,
+    /// pairs of adjacent characters typed with different hands
+    pub alternating_pairs: usize,
+    /// pairs of adjacent characters each typed with one hand
+    pub pairs: usize,
+    /// characters which can not be typed directly
+    pub missing: usize,
+}
+
+impl TypingCost {
+    /// Work out the cost of typing `text` on `layout`.
+    ///
+    /// On a physical keyboard, shift or Alt Gr is held down across a run of characters which need it,
+    /// while a phone's shift key is pressed again for every capital.
+    /// Switching from a phone's letters to its second layer of symbols takes two presses, any other switch one.
+    /// Characters which can not be typed directly count as a single keystroke.
+    #[must_use]
+    pub fn new(text: &str, layout: KeyboardLayout) -> Self {
+        let mut cost = Self::default();
+        let mut layer = 0;
+        let mut modified = false;
+        let mut hand = None;
+        for c in text.chars() {
+            cost.keystrokes += 1;
+            let Some(key) = key(c, layout) else {
+                cost.missing += 1;
+                modified = false;
+                hand = None;
+                continue;
+            };
+            if key.layer != layer {
+                cost.layer_switches += if key.layer == 0 {
+                    1
+                } else {
+                    usize::from(key.layer.abs_diff(layer))
+                };
+                layer = key.layer;
+            }
+            if key.modified && (layout == KeyboardLayout::Mobile || !modified) {
+                cost.shifts += 1;
+            }
+            modified = key.modified;
+            if let (Some(previous), Some(current)) = (hand, key.hand) {
+                cost.pairs += 1;
+                cost.alternating_pairs += usize::from(previous != current);
+            }
+            hand = key.hand;
+        }
+        cost.keystrokes += cost.shifts + cost.layer_switches;
+        cost
+    }
+}
+
+impl fmt::Display for TypingCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} keystrokes, {} shift presses, {} layer switches, hands alternate {} of {} times",
+            self.keystrokes, self.shifts, self.layer_switches, self.alternating_pairs, self.pairs
+        )?;
+        if self.missing > 0 {
+            write!(f, ", {} not on the keyboard", self.missing)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUTS: [KeyboardLayout; 5] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Mobile,
+    ];
+
+    #[test]
+    fn test_every_layout_types_every_letter_and_digit() {
+        for layout in LAYOUTS {
+            for c in ('a'..='z').chain('A'..='Z').chain('0'..='9') {
+                assert!(key(c, layout).is_some(), "{c} on {layout}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_rows_line_up() {
+        for layout in LAYOUTS {
+            for row in rows(layout) {
+                let len = row.levels[0].chars().count();
+                assert!(
+                    row.levels.iter().all(|level| level.chars().count() <= len),
+                    "{} on {layout}",
+                    row.levels[0]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let qwerty = KeyboardLayout::Qwerty;
+        assert_eq!(
+            key('q', qwerty),
+            Some(Key {
+                layer: 0,
+                modified: false,
+                hand: Some(Hand::Left)
+            })
+        );
+        assert_eq!(
+            key('P', qwerty).map(|key| key.hand),
+            Some(Some(Hand::Right))
+        );
+        assert!(key('1', KeyboardLayout::Azerty).is_some_and(|key| key.modified));
+        assert!(key('@', KeyboardLayout::Qwertz).is_some_and(|key| key.modified));
+        assert_eq!(
+            key('#', KeyboardLayout::Mobile).map(|key| key.layer),
+            Some(2)
+        );
+        assert_eq!(key('é', qwerty), None);
+    }
+
+    #[test]
+    fn test_is_easy() {
+        assert!(is_easy('-', KeyboardLayout::Qwerty));
+        assert!(!is_easy('!', KeyboardLayout::Qwerty));
+        assert!(is_easy('!', KeyboardLayout::Azerty));
+        assert!(is_easy('!', KeyboardLayout::Mobile));
+        assert!(!is_easy('^', KeyboardLayout::Mobile));
+        assert!(is_easy_word("Staple", KeyboardLayout::Dvorak));
+        assert!(!is_easy_word("yo-yo", KeyboardLayout::Mobile));
+        assert!(is_easy_word("été", KeyboardLayout::Azerty));
+        assert!(!is_easy_word("été", KeyboardLayout::Qwerty));
+    }
+
+    #[test]
+    fn test_typing_cost_desktop() {
+        // shift is held down across HORSE
+        let cost = TypingCost::new("HORSE-ox", KeyboardLayout::Qwerty);
+        assert_eq!(cost.shifts, 1);
+        assert_eq!(cost.layer_switches, 0);
+        assert_eq!(cost.keystrokes, 9);
+        assert_eq!(cost.pairs, 7);
+        // the hands alternate on O R, E - and o x
+        assert_eq!(cost.alternating_pairs, 3);
+        let cost = TypingCost::new("a b", KeyboardLayout::Qwerty);
+        assert_eq!(cost.pairs, 0);
+    }
+
+    #[test]
+    fn test_typing_cost_mobile() {
+        // one shift, then one switch to the digits, one back, two to #+= and one back
+        let cost = TypingCost::new("Ab1c#d", KeyboardLayout::Mobile);
+        assert_eq!(cost.shifts, 1);
+        assert_eq!(cost.layer_switches, 5);
+        assert_eq!(cost.keystrokes, 12);
+        let cost = TypingCost::new("AB", KeyboardLayout::Mobile);
+        assert_eq!(cost.shifts, 2);
+    }
+
+    #[test]
+    fn test_typing_cost_missing() {
+        let cost = TypingCost::new("naïve", KeyboardLayout::Qwerty);
+        assert_eq!(cost.missing, 1);
+        assert_eq!(cost.keystrokes, 5);
+        assert_eq!(cost.pairs, 2);
+        assert!(cost.to_string().ends_with(", 1 not on the keyboard"));
+    }
+}
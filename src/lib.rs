@@ -2,9 +2,16 @@
 //!
 //! Note that this library *could* be used by a 3rd party crate, but the intended purpose
 //! is to support the binaries associated with this crate.
+pub mod cli;
 pub mod config;
 pub mod consts;
+pub mod entropy;
+mod golden_tests;
+pub mod keyboard;
 pub mod password_maker;
+pub mod policy;
+pub mod secret;
 pub mod test_helpers;
 pub mod types;
 pub mod word_transformer;
+pub mod wordlist;
@@ -19,118 +19,267 @@
 //!
 //! Use custom configurations, or roll with the defaults.
 use std::env;
+use std::fs;
+use std::io;
+use std::io::Write;
 use std::process::ExitCode;
 
+use getopts::Matches;
 use getopts::Options;
+#[cfg(debug_assertions)]
+use rand::SeedableRng;
 use rand::rngs::OsRng;
+#[cfg(debug_assertions)]
+use rand::rngs::SmallRng;
 use rand::rngs::ThreadRng;
 
+use fmn_passgen::cli;
+use fmn_passgen::config::Config;
 use fmn_passgen::config::ConfigBuilder;
-use fmn_passgen::consts::default;
+use fmn_passgen::entropy::Entropy;
+use fmn_passgen::keyboard::TypingCost;
 use fmn_passgen::password_maker::PasswordMaker;
-use fmn_passgen::types::PaddingType;
+use fmn_passgen::password_maker::slot_wordlists;
+use fmn_passgen::password_maker::wordlist_for;
+use fmn_passgen::policy::PolicyError;
+use fmn_passgen::secret::SecretString;
+use fmn_passgen::types::Command;
+use fmn_passgen::types::ConfigAction;
+use fmn_passgen::types::KeyboardLayout;
+use fmn_passgen::types::Preset;
 use fmn_passgen::types::RngType;
-use fmn_passgen::types::WordTransformationType;
+use fmn_passgen::types::Shell;
+use fmn_passgen::types::StrEnum;
+use fmn_passgen::wordlist;
+use fmn_passgen::wordlist::WeightedWordlist;
+
+/// Environment variable which makes the output reproducible for the integration tests.
+#[cfg(debug_assertions)]
+const TEST_SEED_VAR: &str = "FMN_PASSGEN_TEST_SEED";
+
+/// Make passwords with the configured RNG.
+///
+/// Debug builds may instead use a seeded RNG, see [`TEST_SEED_VAR`].
+/// Release builds never do, so that their output can not be made predictable.
+fn make_passwords(config: Config) -> Result<Vec<SecretString>, PolicyError> {
+    #[cfg(debug_assertions)]
+    if let Some(seed) = env::var(TEST_SEED_VAR)
+        .ok()
+        .and_then(|seed| seed.parse::<u64>().ok())
+    {
+        return PasswordMaker::with_rng(SmallRng::seed_from_u64(seed), config).make_passwords();
+    }
+    match config.rng_type {
+        RngType::OsRng => PasswordMaker::<OsRng>::new(config).make_passwords(),
+        RngType::Csprng => PasswordMaker::<ThreadRng>::new(config).make_passwords(),
+    }
+}
+
+/// Print the help message.
+fn print_help(program_name: &str, opts: &Options) {
+    println!("{}", opts.usage(&cli::brief(program_name)));
+    println!("{}", cli::types_help());
+}
+
+/// Write each password on its own line.
+fn print_passwords(passwords: Vec<SecretString>) -> ExitCode {
+    let mut stdout = io::stdout().lock();
+    for mut password in passwords {
+        // Writing the password and its newline in one go lets stdout's line buffering
+        // pass our buffer straight through instead of keeping its own copy around.
+        password.push('\n');
+        if let Err(e) = stdout.write_all(password.as_bytes()) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Write each password on its own line, followed by a tab and how costly it is to type on the configured keyboard.
+fn print_typing_costs(passwords: Vec<SecretString>, keyboard: KeyboardLayout) -> ExitCode {
+    let mut stdout = io::stdout().lock();
+    for mut password in passwords {
+        let cost = TypingCost::new(&password, keyboard).to_string();
+        // the whole line goes out in one write, as in print_passwords
+        password.push('\t');
+        password.push_str(&cost);
+        password.push('\n');
+        if let Err(e) = stdout.write_all(password.as_bytes()) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Warn about settings in `config` which make for weaker passwords than they seem to.
+fn warn(config: &Config) {
+    for conflict in config.policy.conflicts(config) {
+        eprintln!("warning: {conflict}");
+    }
+    if !config.custom_wordlist.is_empty() {
+        for problem in wordlist::check(&config.custom_wordlist) {
+            eprintln!("warning: custom wordlist: {problem}");
+        }
+    }
+    if config.joins_words() && !config.drops_prefixes() {
+        let wordlists = slot_wordlists(config, &wordlist_for(config));
+        if !wordlist::is_uniquely_decodable(&allowed_words(&wordlists, config)) {
+            eprintln!(
+                "warning: words run together and some read as others, consider --joined-words prefix-free"
+            );
+        }
+    }
+}
+
+/// Every word any slot's `wordlists` allow under `config`, sorted and without duplicates.
+fn allowed_words<'a>(wordlists: &'a [Vec<WeightedWordlist>], config: &Config) -> Vec<&'a str> {
+    let mut words = wordlists
+        .iter()
+        .flatten()
+        .flat_map(|wordlist| wordlist::allowed_words(&wordlist.words, config))
+        .collect::<Vec<&str>>();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+/// Estimate the strength of passwords made with `config` and its wordlists.
+///
+/// When words run together, account for those which read as others.
+/// When avoiding ambiguous characters, or using a preset, also work out what that costs by
+/// rebuilding the config from `matches` without doing so.
+fn entropy(config: &Config, matches: &Matches) -> Entropy {
+    let estimate = |config: &Config| {
+        let wordlists = slot_wordlists(config, &wordlist_for(config));
+        let length_counts = wordlists
+            .iter()
+            .map(|wordlists| wordlist::mixed_length_counts(wordlists, config))
+            .collect::<Vec<_>>();
+        let result = Entropy::for_slots(config, &length_counts);
+        if config.joins_words() {
+            result.with_join_loss(wordlist::join_loss(&allowed_words(&wordlists, config)))
+        } else {
+            result
+        }
+    };
+    let baseline = |without: fn(ConfigBuilder) -> ConfigBuilder| {
+        cli::config_builder(matches)
+            .ok()
+            .and_then(|builder| without(builder).build().ok())
+            .map(|baseline| estimate(&baseline))
+    };
+    let mut result = estimate(config);
+    if config.avoid_ambiguous
+        && let Some(baseline) = baseline(|builder| builder.avoid_ambiguous(None))
+    {
+        result = result.with_ambiguity_baseline(&baseline);
+    }
+    if config.preset != Preset::default()
+        && let Some(baseline) = baseline(|builder| builder.preset(None))
+    {
+        result = result.with_preset_baseline(config.preset, &baseline);
+    }
+    result
+}
+
+/// Show statistics for the configured wordlist, or check and show statistics for the one at `path`.
+///
+/// With `check`, the configured wordlist is checked too.
+fn wordlist(config: &Config, check: bool, path: Option<&String>) -> ExitCode {
+    let (name, words) = match path {
+        None => (config.wordlist_name, wordlist_for(config).to_vec()),
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => (path.as_str(), wordlist::parse(&text)),
+            Err(e) => {
+                eprintln!("{path}: {e}");
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let problems = if check || path.is_some() {
+        wordlist::check(&words)
+    } else {
+        Vec::new()
+    };
+    for problem in &problems {
+        eprintln!("{name}: {problem}");
+    }
+    print!("{}", wordlist::Stats::new(&words, config));
+    if problems.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Print the completion script for the shell named by `argument`.
+fn completions(argument: Option<&String>) -> ExitCode {
+    let shell = argument.map_or("", String::as_str);
+    match Shell::to_member(shell) {
+        Ok(shell) => {
+            print!("{}", cli::completions(*shell));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Build the [`Config`] from `matches`, warn about it, and run `command` with it.
+fn with_config(matches: &Matches, command: impl FnOnce(Config) -> ExitCode) -> ExitCode {
+    match cli::config_builder(matches)
+        .and_then(|builder| builder.build().map_err(|e| e.to_string()))
+    {
+        Ok(config) => {
+            warn(&config);
+            command(config)
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Print, check or export `config`, as the `argument` to the `config` subcommand asks.
+fn config_command(config: &Config, argument: Option<&String>) -> ExitCode {
+    let action = argument.map_or_else(
+        || Ok(ConfigAction::default()),
+        |action| ConfigAction::to_member(action).copied(),
+    );
+    match action {
+        Ok(ConfigAction::Print) => print!("{config}"),
+        Ok(ConfigAction::Check) => {}
+        Ok(ConfigAction::Export) => match cli::export(config) {
+            Ok(command) => println!("{command}"),
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
 
 /// The entrypoint.
 ///
-/// Here, we define the program's CLI arguments.
+/// Here, we define the program's CLI arguments, see [`cli`].
 /// We use the [`getopts` library](https://docs.rs/getopts/latest/getopts/) to accomplish this.
-/// We check which arguments the user passed in and create a [`config::Config`].
-/// Finally, we generate passwords using the specified configuration.
-#[expect(
-    clippy::too_many_lines,
-    reason = "As long as it is fairly simple and readable..."
-)]
+/// We check which subcommand and arguments the user passed in and create a [`Config`].
+/// Finally, we run the subcommand, which by default generates passwords using the specified configuration.
 fn main() -> ExitCode {
-    // TODO this ought to be const
-    let default_symbol_alphabet_help: String = format!(
-        "CHOICES, default=\"{}\"",
-        default::SYMBOL_ALPHABET.into_iter().collect::<String>()
-    );
-
     let args: Vec<String> = env::args().collect();
     let program_name = args[0].clone();
 
-    let mut opts = Options::new();
-    opts.optflag("h", "help", "");
-    opts.optopt(
-        "c",
-        "count",
-        "how many passwords to make",
-        &format!("NUM, default={}", default::COUNT),
-    );
-    opts.optopt(
-        "w",
-        "word-count",
-        "number of words",
-        &format!("NUM, default={}", default::WORD_COUNT),
-    );
-    opts.optopt(
-        "m",
-        "word-min-length",
-        "minimum length of a chosen word",
-        &format!("NUM, default={}", default::WORD_MIN_LENGTH),
-    );
-    opts.optopt(
-        "M",
-        "word-max-length",
-        "maximum length of a chosen word",
-        &format!("NUM, default={}", default::WORD_MAX_LENGTH),
-    );
-    opts.optopt(
-        "W",
-        "word-transformation",
-        "transformation to apply to the selected words",
-        &format!("TYPE, default={}", &WordTransformationType::default()),
-    );
-    opts.optopt(
-        "b",
-        "digits-before",
-        "number of digits to prepend",
-        &format!("NUM, default={}", default::DIGITS_BEFORE),
-    );
-    opts.optopt(
-        "a",
-        "digits-after",
-        "number of digits to append",
-        &format!("NUM, default={}", default::DIGITS_AFTER),
-    );
-    opts.optopt(
-        "T",
-        "padding-type",
-        "how to apply padding",
-        &format!("TYPE, default={}", &PaddingType::default()),
-    );
-    opts.optopt(
-        "l",
-        "padding-length",
-        "how much to pad",
-        &format!(
-            "NUM, default={} for fixed, {} for adaptive",
-            default::PADDING_LENGTH_FIXED,
-            default::PADDING_LENGTH_ADAPTIVE
-        ),
-    );
-    opts.optopt(
-        "p",
-        "padding-characters",
-        "list of characters to choose from",
-        &default_symbol_alphabet_help,
-    );
-    opts.optopt(
-        "s",
-        "separators",
-        "list of characters to choose from",
-        &default_symbol_alphabet_help,
-    );
-    opts.optopt(
-        "r",
-        "rng",
-        "method of random number generation",
-        &format!("TYPE, default={}", &RngType::default()),
-    );
-
+    let opts = cli::getopts_options();
     let matches = match opts.parse(&args[1..]) {
         Ok(v) => v,
         Err(failure) => {
@@ -139,68 +288,59 @@ fn main() -> ExitCode {
         }
     };
 
-    // if the help flag is present or if there are unused arguments, display a help message.
-    if matches.opt_present("h") || !matches.free.is_empty() {
-        let brief = format!("Usage: {program_name} [options]");
-        println!("{}", opts.usage(&brief));
-        println!("types are case insensitive");
-        // TODO make less brittle, see crate::types
-        println!("\nWORD TRANSFORMATIONS:");
-        println!("    none");
-        println!("    lower                   (correct horse battery staple)");
-        println!("    upper                   (CORRECT HORSE BATTERY STAPLE)");
-        println!("    capitalize-first        (Correct Horse Battery Staple)");
-        println!("    capitalize-last         (correcT horsE batterY staplE)");
-        println!("    capitalize-not-first    (cORRECT hORSE bATTERY sTAPLE)");
-        println!("    alternating-lower-upper (correct HORSE battery STAPLE)");
-        println!("    alternating-upper-lower (CORRECT horse BATTERY staple)");
-        println!("    random-upper-lower      (correct HORSE battery staple)");
-        println!("\nPADDING TYPES:");
-        println!("    none");
-        println!("    fixed-front    (add padding-length padding-characters to front)");
-        println!("    fixed-back     (add padding-length padding-characters to back)");
-        println!("    fixed-both     (add padding-length padding-characters to front and back)");
-        println!("    fixed          (alias for fixed-both)");
-        println!("    adaptive-front (if length of unpadded password is less than padding-length,");
-        println!("                    prepend padding-characters to meet length)");
-        println!("    adaptive-back  (if length of unpadded password is less than padding-length,");
-        println!("                    append padding-characters to meet length)");
-        println!("    adaptive       (alias for adaptive-back)");
-        println!("\nRNG TYPES:");
-        println!("    os-rng (the system's native secure RNG)");
-        println!("    csprng (a reasonably secure userspace RNG)");
+    if matches.opt_present("man") {
+        print!("{}", cli::man_page());
         return ExitCode::SUCCESS;
     }
 
-    // TODO this boilerplate could be reduced
-    let config_builder = ConfigBuilder::new()
-        .count(matches.opt_str("count"))
-        .word_count(matches.opt_str("word-count"))
-        .word_min_length(matches.opt_str("word-min-length"))
-        .word_max_length(matches.opt_str("word-max-length"))
-        .word_transformation(matches.opt_str("word-transformation"))
-        .digits_before(matches.opt_str("digits-before"))
-        .digits_after(matches.opt_str("digits-after"))
-        .padding_type(matches.opt_str("padding-type"))
-        .padding_length(matches.opt_str("padding-length"))
-        .padding_characters(matches.opt_str("padding-characters"))
-        .separator_characters(matches.opt_str("separators"))
-        .rng_type(matches.opt_str("rng"));
-
-    match config_builder.build() {
-        Err(e) => {
-            eprintln!("{e}");
-            ExitCode::FAILURE
+    // without a command, generate passwords as before subcommands existed
+    let command = matches
+        .free
+        .first()
+        .map_or(Some(Command::Generate), |name| {
+            Command::to_member(name).ok().copied()
+        });
+    let arguments = command.and_then(|command| {
+        cli::command(command).arguments(matches.free.get(1..).unwrap_or_default())
+    });
+    // if the help flag is present or if there are unexpected arguments, display a help message.
+    let (false, Some(command), Some((action, argument))) =
+        (matches.opt_present("h"), command, arguments)
+    else {
+        print_help(&program_name, &opts);
+        return ExitCode::SUCCESS;
+    };
+
+    match command {
+        Command::Completions => completions(argument),
+        Command::Generate | Command::Entropy if matches.opt_present("explain") => {
+            with_config(&matches, |config| {
+                print!("{}", entropy(&config, &matches).explain());
+                ExitCode::SUCCESS
+            })
         }
-        Ok(config) => {
-            let result = match config.rng_type {
-                RngType::OsRng => PasswordMaker::<OsRng>::new(config).make_passwords(),
-                RngType::Csprng => PasswordMaker::<ThreadRng>::new(config).make_passwords(),
-            };
-            for password in result {
-                println!("{password}");
+        Command::Generate => with_config(&matches, |config| match make_passwords(config) {
+            Ok(passwords) => print_passwords(passwords),
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        }),
+        Command::Typing => with_config(&matches, |config| {
+            let keyboard = config.keyboard;
+            match make_passwords(config) {
+                Ok(passwords) => print_typing_costs(passwords, keyboard),
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
             }
+        }),
+        Command::Entropy => with_config(&matches, |config| {
+            print!("{}", entropy(&config, &matches));
             ExitCode::SUCCESS
-        }
+        }),
+        Command::Wordlist => with_config(&matches, |config| wordlist(&config, action, argument)),
+        Command::Config => with_config(&matches, |config| config_command(&config, argument)),
     }
 }
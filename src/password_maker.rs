@@ -2,21 +2,227 @@
 //!
 //! The password generation algorithm is implemented here.
 // provides:
-// static WORDLIST: &[&str] = &[...]
+// static WORDLIST_NAMES: &[&str] = &[...]
+// static WORDLISTS: &[(&str, &str, &[u8], &str)] = &[...]
 include!(concat!(env!("OUT_DIR"), "/wordlist.rs"));
 
 use std::iter;
+use std::sync::Arc;
+use std::sync::LazyLock;
 
 use rand::TryRngCore;
+use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
 use rand_core::UnwrapErr;
+use zeroize::Zeroizing;
 
 use crate::config::Config;
 use crate::config::ConfigBuilder;
-use crate::consts::DIGIT_ALPHABET;
+use crate::policy::MAX_ATTEMPTS;
+use crate::policy::PolicyError;
+use crate::secret::SecretString;
+use crate::types::DigitPlacement;
+use crate::types::KeyboardLayout;
+use crate::types::PaddingFill;
 use crate::types::PaddingType;
+use crate::types::PartOfSpeech;
+use crate::types::SeparatorType;
+use crate::types::StrEnum;
+use crate::types::WordSource;
 use crate::types::WordTransformationType;
 use crate::word_transformer;
+use crate::wordlist;
+use crate::wordlist::WeightedWordlist;
+
+/// Identifies the sequence of random choices [`PasswordMaker::make_password`] makes.
+///
+/// Given the same seed, config and wordlist, passwords are reproducible for as long as this is unchanged.
+/// Anything that changes what is drawn from the RNG, or in which order, must bump this version
+/// and regenerate the golden test vectors.
+pub const GENERATOR_VERSION: u32 = 1;
+
+/// The compile-time wordlists, unpacked once and shared by every [`PasswordMaker`].
+static BUILTIN_WORDLISTS: LazyLock<Vec<Arc<[Box<str>]>>> = LazyLock::new(|| {
+    WORDLISTS
+        .iter()
+        .map(|(_, _, lengths, packed)| unpack(lengths, packed))
+        .collect()
+});
+
+/// The built-in words for each [`PartOfSpeech`], in declaration order, see [`part_of_speech_wordlist`].
+static GRAMMAR_WORDLISTS: LazyLock<Vec<Arc<[Box<str>]>>> = LazyLock::new(|| {
+    let text = include_str!("../wordlists/grammar.txt");
+    PartOfSpeech::NAMES
+        .iter()
+        .map(|name| {
+            text.lines()
+                .filter_map(|line| line.split_once(' '))
+                .filter(|(tag, _)| tag == name)
+                .map(|(_, word)| Box::from(word))
+                .collect()
+        })
+        .collect()
+});
+
+/// Split `packed` into words of the given `lengths`, in bytes.
+fn unpack(lengths: &[u8], packed: &str) -> Arc<[Box<str>]> {
+    let mut rest = packed;
+    lengths
+        .iter()
+        .map(|&len| {
+            let (word, tail) = rest.split_at(len.into());
+            rest = tail;
+            Box::from(word)
+        })
+        .collect()
+}
+
+/// The names of the wordlists built into this binary, the default first.
+#[must_use]
+pub fn builtin_wordlist_names() -> &'static [&'static str] {
+    WORDLIST_NAMES
+}
+
+/// The name, description and size of each wordlist built into this binary, the default first.
+pub fn builtin_wordlists() -> impl Iterator<Item = (&'static str, &'static str, usize)> {
+    WORDLISTS
+        .iter()
+        .map(|(name, description, lengths, _)| (*name, *description, lengths.len()))
+}
+
+/// Get a cheap, shared handle to the built-in wordlist called `name`.
+#[must_use]
+pub fn builtin_wordlist(name: &str) -> Option<Arc<[Box<str>]>> {
+    let i = WORDLIST_NAMES.iter().position(|n| *n == name)?;
+    Some(Arc::clone(&BUILTIN_WORDLISTS[i]))
+}
+
+/// Get a cheap, shared handle to the default wordlist.
+#[must_use]
+pub fn default_wordlist() -> Arc<[Box<str>]> {
+    // the build script makes sure there is at least one
+    Arc::clone(&BUILTIN_WORDLISTS[0])
+}
+
+/// Get a cheap, shared handle to the wordlist `config` names, or the default one if it names none.
+#[must_use]
+pub fn wordlist_for(config: &Config) -> Arc<[Box<str>]> {
+    builtin_wordlist(config.wordlist_name).unwrap_or_else(default_wordlist)
+}
+
+/// Get a cheap, shared handle to the built-in words which are `part` of speech.
+///
+/// They are taken from the EFF large wordlist, verbs in the past tense so that a template reads as a sentence.
+#[must_use]
+pub fn part_of_speech_wordlist(part: PartOfSpeech) -> Arc<[Box<str>]> {
+    Arc::clone(&GRAMMAR_WORDLISTS[part as usize])
+}
+
+/// Get a cheap, shared handle to the words `source` names under `config`.
+///
+/// `wordlist` stands in for a built-in wordlist which is missing from this binary,
+/// which [`ConfigBuilder`] never lets through.
+#[must_use]
+pub fn source_wordlist(
+    source: WordSource,
+    config: &Config,
+    wordlist: &Arc<[Box<str>]>,
+) -> Arc<[Box<str>]> {
+    match source {
+        WordSource::Builtin(name) => builtin_wordlist(name).unwrap_or_else(|| Arc::clone(wordlist)),
+        WordSource::Part(part) => part_of_speech_wordlist(part),
+        WordSource::Custom => Arc::clone(&config.custom_wordlist),
+    }
+}
+
+/// The wordlists each word slot draws from: those of the slot wordlists, one for each part of speech
+/// in the template, or else `wordlist` for each of `config`'s words.
+#[must_use]
+pub fn slot_wordlists(config: &Config, wordlist: &Arc<[Box<str>]>) -> Vec<Vec<WeightedWordlist>> {
+    let single = |words| vec![WeightedWordlist { words, weight: 1 }];
+    if !config.slot_wordlists.is_empty() {
+        config
+            .slot_wordlists
+            .iter()
+            .map(|slot| {
+                slot.iter()
+                    .map(|source| WeightedWordlist {
+                        words: source_wordlist(source.source, config, wordlist),
+                        weight: source.weight,
+                    })
+                    .collect()
+            })
+            .collect()
+    } else if !config.template.is_empty() {
+        config
+            .template
+            .iter()
+            .map(|part| single(part_of_speech_wordlist(*part)))
+            .collect()
+    } else {
+        vec![single(Arc::clone(wordlist)); config.word_count as usize]
+    }
+}
+
+/// The result of [`PasswordMaker::filter_wordlist`] along with everything it was derived from.
+///
+/// If any of these inputs change, the indices are stale and must be recomputed.
+#[derive(Debug, Clone)]
+struct FilterCache {
+    wordlist: Arc<[Box<str>]>,
+    word_min_length: u8,
+    word_max_length: u8,
+    word_transformation: WordTransformationType,
+    avoid_ambiguous: bool,
+    /// the keyboard, if typing easily
+    easy_typing: Option<KeyboardLayout>,
+    blocklist: bool,
+    excluded_words: Vec<Box<str>>,
+    drops_prefixes: bool,
+    indices: Arc<[u32]>,
+}
+
+impl FilterCache {
+    fn is_valid_for(&self, wordlist: &Arc<[Box<str>]>, config: &Config) -> bool {
+        Arc::ptr_eq(&self.wordlist, wordlist)
+            && self.word_min_length == config.word_min_length
+            && self.word_max_length == config.word_max_length
+            && self.word_transformation == config.word_transformation
+            && self.avoid_ambiguous == config.avoid_ambiguous
+            && self.easy_typing == config.easy_typing.then_some(config.keyboard)
+            && self.blocklist == config.blocklist
+            && self.excluded_words == config.excluded_words
+            && self.drops_prefixes == config.drops_prefixes()
+    }
+}
+
+/// One of a word slot's wordlists, the indices of the words in it which may be chosen,
+/// and how likely each of them is relative to the words of the slot's other wordlists.
+#[derive(Debug, Clone)]
+struct SlotPart {
+    wordlist: Arc<[Box<str>]>,
+    indices: Arc<[u32]>,
+    weight: u8,
+}
+
+/// The wordlists a word slot draws from, leaving out those with no words which may be chosen.
+#[derive(Debug, Clone)]
+struct Slot {
+    parts: Vec<SlotPart>,
+}
+
+/// A chosen word, as the index of a [`SlotPart`] and the index of the word in its wordlist.
+type Choice = (usize, u32);
+
+/// The words a [`SlotPart`] may still draw, along with how likely the part is to be drawn from.
+type Candidates = (u64, Arc<[u32]>);
+
+impl Slot {
+    /// The word `choice` stands for.
+    fn word(&self, (part, i): Choice) -> &str {
+        &self.parts[part].wordlist[i as usize]
+    }
+}
 
 /// Turn a [`Config`] into passwords.
 #[derive(Debug)]
@@ -28,8 +234,10 @@ where
     pub rng: UnwrapErr<T>,
     /// A config
     pub config: Config,
-    /// A wordlist
-    pub wordlist: Vec<String>,
+    /// The wordlist words are drawn from, unless the config has a template
+    pub wordlist: Arc<[Box<str>]>,
+    /// Filtered word indices for each wordlist in use, reused until `config` or the wordlist change
+    filter_caches: Vec<FilterCache>,
 }
 
 impl<T> Default for PasswordMaker<T>
@@ -42,17 +250,12 @@ where
             reason = "we control this default and it must not fail"
         )]
         let config = ConfigBuilder::new().build().unwrap();
-        Self {
-            rng: T::default().unwrap_err(),
-            wordlist: WORDLIST.iter().map(|s| String::from(*s)).collect(),
-            config,
-        }
+        Self::new(config)
     }
 }
 
 /// Note that [`rand_core::SeedableRng`] does not impl [`Default`].
-/// This is a small struct, creating an instance without a `new` method
-/// is not too bad, and `SeedableRng` is only useful for testing.
+/// Use [`PasswordMaker::with_rng`] to provide an already constructed RNG instead.
 impl<T> PasswordMaker<T>
 where
     T: TryRngCore + Default,
@@ -60,11 +263,7 @@ where
     #[must_use]
     /// Make a new [`PasswordMaker`]
     pub fn new(config: Config) -> Self {
-        Self {
-            rng: T::default().unwrap_err(),
-            config,
-            wordlist: WORDLIST.iter().map(|s| String::from(*s)).collect(),
-        }
+        Self::with_rng(T::default(), config)
     }
 }
 
@@ -72,7 +271,20 @@ impl<T> PasswordMaker<T>
 where
     T: TryRngCore,
 {
-    /// Filter out words that do not fit between the configured minimum and maximum length.
+    #[must_use]
+    /// Make a new [`PasswordMaker`] from an existing RNG, such as a seeded one.
+    pub fn with_rng(rng: T, config: Config) -> Self {
+        let wordlist = wordlist_for(&config);
+        Self {
+            rng: rng.unwrap_err(),
+            config,
+            wordlist,
+            filter_caches: Vec::new(),
+        }
+    }
+    /// Filter out words in `wordlist` that do not fit between the configured minimum and maximum length,
+    /// that are blocked, or that could be misread once transformed when avoiding ambiguous characters.
+    /// When words run together and must be prefix free, also filter out words which start another.
     ///
     /// Return indexes indicating which words we wish to keep.
     /// Working with indexes avoids pointer hell and reduces memory allocation and storage requirements.
@@ -80,38 +292,230 @@ where
         clippy::cast_possible_truncation,
         reason = "u32 MAX is more than enough for any reasonable word list length"
     )]
-    fn filter_wordlist(&self) -> Vec<u32> {
-        let min_len = self.config.word_min_length as usize;
-        let max_len = self.config.word_max_length as usize;
-        self.wordlist
+    fn filter_wordlist(&self, wordlist: &[Box<str>]) -> Vec<u32> {
+        let indices = wordlist
             .iter()
             .enumerate()
-            .filter(|(_, word)| (min_len..=max_len).contains(&word.chars().count()))
+            .filter(|(_, word)| wordlist::is_word_allowed(word, &self.config))
             .map(|(i, _)| i as u32)
+            .collect::<Vec<u32>>();
+        if !self.config.drops_prefixes() {
+            return indices;
+        }
+        let words = indices
+            .iter()
+            .map(|i| &*wordlist[*i as usize])
+            .collect::<Vec<&str>>();
+        indices
+            .into_iter()
+            .zip(wordlist::starts_another(&words))
+            .filter(|(_, starts_another)| !starts_another)
+            .map(|(i, _)| i)
             .collect()
     }
-    /// Choose with replacement a configured number of words.
-    ///
-    /// Convert each chosen word from an index into a [`String`].
-    fn choose_words(&mut self, indices: &[u32]) -> Vec<String> {
-        if indices.is_empty() {
-            return Vec::new();
+    /// Get the result of [`Self::filter_wordlist`] for `wordlist`, only recomputing it if the
+    /// wordlist or the configuration it depends on has changed since the last call.
+    fn filtered_word_indices(&mut self, wordlist: &Arc<[Box<str>]>) -> Arc<[u32]> {
+        if let Some(cache) = self
+            .filter_caches
+            .iter()
+            .find(|cache| cache.is_valid_for(wordlist, &self.config))
+        {
+            return Arc::clone(&cache.indices);
         }
-        let n = self.config.word_count as usize;
-        let mut buf = Vec::with_capacity(n);
-        for _ in 0..n {
-            buf.push(indices.choose(&mut self.rng).expect(concat!(
-                "invariant 1: `indices` must not be empty and should have been guarded above.\n",
-                "invariant 2: size_hint on a slice iterator with no intermediary ",
-                "iterator adapters should always be accurate.",
-            )));
-        }
-        buf.into_iter()
-            .map(|n| self.wordlist[*n as usize].clone())
+        let indices: Arc<[u32]> = self.filter_wordlist(wordlist).into();
+        self.filter_caches
+            .retain(|cache| !Arc::ptr_eq(&cache.wordlist, wordlist));
+        self.filter_caches.push(FilterCache {
+            wordlist: Arc::clone(wordlist),
+            word_min_length: self.config.word_min_length,
+            word_max_length: self.config.word_max_length,
+            word_transformation: self.config.word_transformation,
+            avoid_ambiguous: self.config.avoid_ambiguous,
+            easy_typing: self.config.easy_typing.then_some(self.config.keyboard),
+            blocklist: self.config.blocklist,
+            excluded_words: self.config.excluded_words.clone(),
+            drops_prefixes: self.config.drops_prefixes(),
+            indices: Arc::clone(&indices),
+        });
+        indices
+    }
+    /// The words each word slot may draw from, see [`slot_wordlists`].
+    fn slots(&mut self) -> Vec<Slot> {
+        slot_wordlists(&self.config, &self.wordlist)
+            .into_iter()
+            .map(|wordlists| Slot {
+                parts: wordlists
+                    .into_iter()
+                    .map(|wordlist| SlotPart {
+                        indices: self.filtered_word_indices(&wordlist.words),
+                        wordlist: wordlist.words,
+                        weight: wordlist.weight,
+                    })
+                    .filter(|part| !part.indices.is_empty())
+                    .collect(),
+            })
             .collect()
     }
-    /// Use the configured [`WordTransformationType`] to transform a [`Vec<String>`] of words.
-    fn transform_words(&mut self, words: Vec<String>) -> Vec<String> {
+    /// Choose one of `indices`.
+    fn choose_index(&mut self, indices: &[u32]) -> u32 {
+        *indices.choose(&mut self.rng).expect(concat!(
+            "invariant 1: `indices` must not be empty and should have been guarded by the caller.\n",
+            "invariant 2: size_hint on a slice iterator with no intermediary ",
+            "iterator adapters should always be accurate.",
+        ))
+    }
+    /// Choose one of a slot's `candidates`, given as the indices left in each of its parts
+    /// along with how likely that part is to be drawn from.
+    ///
+    /// A slot with a single part draws from it without spending any randomness on which part to draw.
+    fn choose_candidate(&mut self, candidates: &[Candidates]) -> Choice {
+        let part = if candidates.len() == 1 {
+            0
+        } else {
+            WeightedIndex::new(candidates.iter().map(|(weight, _)| *weight))
+                .expect("invariant: some part has candidates and should have been guarded by the caller")
+                .sample(&mut self.rng)
+        };
+        (part, self.choose_index(&candidates[part].1))
+    }
+    /// Choose one of the `candidates` of each slot, see [`Self::choose_candidate`].
+    ///
+    /// The choices give the words away, so they are zeroed once dropped.
+    fn choose_candidates(&mut self, candidates: &[Vec<Candidates>]) -> Zeroizing<Vec<Choice>> {
+        Zeroizing::new(
+            candidates
+                .iter()
+                .map(|candidates| self.choose_candidate(candidates))
+                .collect(),
+        )
+    }
+    /// The words each of `slots` may draw, for each of its parts.
+    ///
+    /// With a word length `budget`, only words that fit next to the shortest words of the other slots are kept.
+    /// Returns [`None`] if some slot is left with nothing to draw.
+    fn candidates(slots: &[Slot], budget: Option<usize>) -> Option<Vec<Vec<Candidates>>> {
+        let word_length = |slot: &Slot, choice: Choice| slot.word(choice).chars().count();
+        // each part of a slot is drawn from in proportion to its weight for each of its candidates
+        let candidates: Vec<Vec<Candidates>> = match budget {
+            Some(budget) => {
+                let shortest = slots
+                    .iter()
+                    .map(|slot| {
+                        slot.parts
+                            .iter()
+                            .enumerate()
+                            .flat_map(|(part, p)| p.indices.iter().map(move |i| (part, *i)))
+                            .map(|choice| word_length(slot, choice))
+                            .min()
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<usize>>();
+                let shortest_total = shortest.iter().sum::<usize>();
+                let mut candidates = Vec::with_capacity(slots.len());
+                for (slot, shortest) in slots.iter().zip(&shortest) {
+                    let longest = (budget + shortest).checked_sub(shortest_total)?;
+                    candidates.push(
+                        slot.parts
+                            .iter()
+                            .enumerate()
+                            .map(|(part, p)| {
+                                let fitting = p
+                                    .indices
+                                    .iter()
+                                    .copied()
+                                    .filter(|i| word_length(slot, (part, *i)) <= longest)
+                                    .collect::<Arc<[u32]>>();
+                                (u64::from(p.weight) * fitting.len() as u64, fitting)
+                            })
+                            .collect(),
+                    );
+                }
+                candidates
+            }
+            None => slots
+                .iter()
+                .map(|slot| {
+                    slot.parts
+                        .iter()
+                        .map(|p| {
+                            (
+                                u64::from(p.weight) * p.indices.len() as u64,
+                                Arc::clone(&p.indices),
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        };
+        // a slot whose words are all too long, or whose parts all weigh nothing, has nothing to draw
+        if candidates
+            .iter()
+            .any(|candidates| candidates.iter().all(|(weight, _)| *weight == 0))
+        {
+            return None;
+        }
+        Some(candidates)
+    }
+    /// Choose a word for each of `slots`.
+    ///
+    /// A slot which mixes wordlists draws each word with a chance in proportion to its wordlist's weight.
+    ///
+    /// When the policy has a maximum length, choices whose lengths add up to more than
+    /// [`Policy::word_length_budget`] are thrown away as a whole and chosen again,
+    /// so every choice that fits stays as likely as it was.
+    /// Words that could not fit even next to the shortest ones are never drawn, which saves attempts.
+    /// Likewise, choices where a blocked word shows up across two adjacent words are chosen again.
+    ///
+    /// Convert each chosen word from an index into a [`SecretString`].
+    /// Returns no words if any slot has none to choose from,
+    /// and [`None`] if a slot has no word that fits or no acceptable choice is found
+    /// within [`MAX_ATTEMPTS`] attempts.
+    ///
+    /// [`Policy::word_length_budget`]: crate::policy::Policy::word_length_budget
+    fn choose_words(&mut self, slots: &[Slot]) -> Option<Vec<SecretString>> {
+        if slots.is_empty() || slots.iter().any(|slot| slot.parts.is_empty()) {
+            return Some(Vec::new());
+        }
+        let to_secrets = |chosen: &[Choice]| {
+            slots
+                .iter()
+                .zip(chosen)
+                .map(|(slot, choice)| SecretString::from(slot.word(*choice)))
+                .collect()
+        };
+        let budget = self.config.policy.word_length_budget(&self.config);
+        let word_length = |slot: &Slot, choice: Choice| slot.word(choice).chars().count();
+        let candidates = Self::candidates(slots, budget)?;
+        if budget.is_none() && !self.config.blocks_words() {
+            let chosen = self.choose_candidates(&candidates);
+            return Some(to_secrets(&chosen));
+        }
+        for _ in 0..MAX_ATTEMPTS {
+            let chosen = self.choose_candidates(&candidates);
+            let fits = budget.is_none_or(|budget| {
+                slots
+                    .iter()
+                    .zip(chosen.iter())
+                    .map(|(slot, choice)| word_length(slot, *choice))
+                    .sum::<usize>()
+                    <= budget
+            });
+            let joins_blocked = (1..slots.len()).any(|i| {
+                wordlist::joins_blocked(
+                    slots[i - 1].word(chosen[i - 1]),
+                    slots[i].word(chosen[i]),
+                    &self.config,
+                )
+            });
+            if fits && !joins_blocked {
+                return Some(to_secrets(&chosen));
+            }
+        }
+        None
+    }
+    /// Use the configured [`WordTransformationType`] to transform a [`Vec<SecretString>`] of words.
+    fn transform_words(&mut self, words: Vec<SecretString>) -> Vec<SecretString> {
         if words.is_empty() {
             return words;
         }
@@ -124,6 +528,9 @@ where
             WordTransformationType::CapitalizeNotFirst => {
                 word_transformer::capitalize_not_first(words)
             }
+            WordTransformationType::CapitalizeFirstWord => {
+                word_transformer::capitalize_first_word(words)
+            }
             WordTransformationType::AlternatingLowerUpper => {
                 word_transformer::alternating_lower_upper(words)
             }
@@ -135,39 +542,128 @@ where
             }
         }
     }
-    /// Choose with replacement `n` digits to form and return an [`Option<String>`].
-    fn choose_n_digits(&mut self, n: usize) -> Option<String> {
-        if n == 0 {
+    /// Choose with replacement `n` of the configured digit characters to form and return an [`Option<SecretString>`].
+    ///
+    /// Returns [`None`] if `n` is 0 or there are no characters to choose from.
+    fn choose_n_digits(&mut self, n: usize) -> Option<SecretString> {
+        if n == 0 || self.config.digit_characters.is_empty() {
             return None;
         }
-        let mut buf = Vec::with_capacity(n);
+        let mut buf = SecretString::with_capacity(n);
         for _ in 0..n {
             #[expect(
                 clippy::unwrap_used,
-                reason = "as DIGIT_ALPHABET is const, it is not empty and should not provide bad size hints"
+                reason = "digit_characters is not empty and a slice provides accurate size hints"
             )]
-            buf.push(DIGIT_ALPHABET.choose(&mut self.rng).unwrap());
+            buf.push(*self.config.digit_characters.choose(&mut self.rng).unwrap());
         }
-        Some(buf.into_iter().collect())
+        Some(buf)
     }
     /// Create the before and after pseudo-words.
-    /// A pseudo-word is a string of 0 or more digits.
-    fn create_pseudo_words(&mut self) -> (Option<String>, Option<String>) {
+    /// A pseudo-word is a string of 0 or more digits, which may be drawn from any [`DigitClass`](crate::types::DigitClass).
+    fn create_pseudo_words(&mut self) -> (Option<SecretString>, Option<SecretString>) {
         let before = self.choose_n_digits(self.config.digits_before as usize);
         let after = self.choose_n_digits(self.config.digits_after as usize);
         (before, after)
     }
-    /// Choose a separator character from the configured choices.
-    fn choose_separator(&mut self) -> Option<char> {
-        self.config
-            .separator_characters
-            .choose(&mut self.rng)
-            .copied()
+    /// Create the pseudo-words and arrange them among `words` according to the [`DigitPlacement`].
+    ///
+    /// Returns the parts to be separated, none of which are empty.
+    fn arrange_parts(&mut self, words: Vec<SecretString>) -> Vec<SecretString> {
+        let word_count = words.len();
+        let placement = if word_count == 0 {
+            // there is nowhere else to put them
+            DigitPlacement::Ends
+        } else {
+            self.config.digit_placement
+        };
+        let parts: Vec<SecretString> = match placement {
+            DigitPlacement::Ends => {
+                let (before, after) = self.create_pseudo_words();
+                iter::once(before.unwrap_or_default())
+                    .chain(words)
+                    .chain(iter::once(after.unwrap_or_default()))
+                    .collect()
+            }
+            DigitPlacement::RandomGap => {
+                let groups: [Option<SecretString>; 2] = self.create_pseudo_words().into();
+                // gap i is just before word i, and gap word_count is after the last word
+                let mut gaps = vec![Vec::new(); word_count + 1];
+                for digits in groups.into_iter().flatten() {
+                    gaps[self.rng.random_range(0..=word_count)].push(digits);
+                }
+                let mut gaps = gaps.into_iter();
+                let mut parts = Vec::with_capacity(word_count + 2);
+                for word in words {
+                    parts.extend(gaps.next().into_iter().flatten());
+                    parts.push(word);
+                }
+                parts.extend(gaps.flatten());
+                parts
+            }
+            DigitPlacement::Attached => {
+                let (before, after) = self.create_pseudo_words();
+                let mut words = words;
+                if let Some(before) = before {
+                    let word = &mut words[self.rng.random_range(0..word_count)];
+                    let mut attached = SecretString::with_capacity(before.len() + word.len());
+                    attached.push_str(&before);
+                    attached.push_str(word);
+                    *word = attached;
+                }
+                if let Some(after) = after {
+                    words[self.rng.random_range(0..word_count)].push_str(&after);
+                }
+                words
+            }
+            DigitPlacement::EveryGap => {
+                let before = self.choose_n_digits(self.config.digits_before as usize);
+                let mut parts = Vec::with_capacity(2 * word_count + 1);
+                parts.extend(before);
+                for word in words {
+                    parts.push(word);
+                    parts.extend(self.choose_n_digits(self.config.digits_after as usize));
+                }
+                parts
+            }
+        };
+        // separator should not apply on empty elements
+        parts.into_iter().filter(|p| !p.is_empty()).collect()
+    }
+    /// Choose the separators for `gaps` gaps between parts, according to the [`SeparatorType`].
+    ///
+    /// Returns fewer separators than gaps, possibly none, when there are no characters to choose from.
+    fn choose_separators(&mut self, gaps: usize) -> SecretString {
+        match self.config.separator_type {
+            SeparatorType::Single => self
+                .config
+                .separator_characters
+                .choose(&mut self.rng)
+                .map(|c| iter::repeat_n(*c, gaps).collect())
+                .unwrap_or_default(),
+            SeparatorType::PerGap => {
+                if self.config.separator_characters.is_empty() {
+                    return SecretString::new();
+                }
+                (0..gaps)
+                    .filter_map(|_| self.config.separator_characters.choose(&mut self.rng))
+                    .copied()
+                    .collect()
+            }
+            SeparatorType::Sequence => self
+                .config
+                .separator_sequence
+                .iter()
+                .copied()
+                .cycle()
+                .take(gaps)
+                .collect(),
+        }
     }
     /// Given the password we have created thus far, create the before and after padding.
     /// Note that if the desired length is shorter than the unpadded password, adaptive
     /// padding is a no-op.
-    fn create_padding(&mut self, password: &str) -> (Option<String>, Option<String>) {
+    fn create_padding(&mut self, password: &str) -> (Option<SecretString>, Option<SecretString>) {
         let len = self.config.padding_length as usize;
         let (before_len, after_len) = match self.config.padding_type {
             PaddingType::None => (0, 0),
@@ -179,21 +675,48 @@ where
                 (0, len.saturating_sub(password.chars().count()))
             }
         };
-        let padding_characters = self.config.padding_characters.choose(&mut self.rng);
-        let mut before: Option<String> =
-            iter::repeat(padding_characters).take(before_len).collect();
-        let mut after: Option<String> = iter::repeat(padding_characters).take(after_len).collect();
+        let rng = &mut self.rng;
+        let [front_characters, back_characters] = self.config.padding_alphabets();
+        let (mut before, mut after): (Option<SecretString>, Option<SecretString>) =
+            match self.config.padding_fill {
+                PaddingFill::Same => {
+                    let padding_character = self.config.padding_characters.choose(rng);
+                    (
+                        padding_character.map(|c| iter::repeat_n(*c, before_len).collect()),
+                        padding_character.map(|c| iter::repeat_n(*c, after_len).collect()),
+                    )
+                }
+                PaddingFill::Independent => {
+                    let front = front_characters.choose(rng);
+                    let back = back_characters.choose(rng);
+                    (
+                        front.map(|c| iter::repeat_n(*c, before_len).collect()),
+                        back.map(|c| iter::repeat_n(*c, after_len).collect()),
+                    )
+                }
+                PaddingFill::Random => {
+                    let mut sequence = |characters: &[char], len: usize| {
+                        (0..len)
+                            .map(|_| characters.choose(&mut *rng).copied())
+                            .collect::<Option<SecretString>>()
+                    };
+                    (
+                        sequence(front_characters, before_len),
+                        sequence(back_characters, after_len),
+                    )
+                }
+            };
         // Some("") and None work about the same in practice, but sticking with one is
         // easier to test and reason about.
-        if let Some(ref v) = before {
-            if v.is_empty() {
-                before = None;
-            }
+        if let Some(ref v) = before
+            && v.is_empty()
+        {
+            before = None;
         }
-        if let Some(ref v) = after {
-            if v.is_empty() {
-                after = None;
-            }
+        if let Some(ref v) = after
+            && v.is_empty()
+        {
+            after = None;
         }
         (before, after)
     }
@@ -202,54 +725,105 @@ where
     /// The password generation algorithm is very similar to the one found in Crypt::HSXKPasswd,
     /// see [https://metacpan.org/pod/Crypt::HSXKPasswd](https://metacpan.org/pod/Crypt::HSXKPasswd) or below for a local copy:
     ///
-    /// 1. Pick random words from the dictionary.
+    /// 1. Pick random words from the dictionary, which fit within the maximum length if there is one.
     /// 2. Apply transformations to the words.
-    /// 3. Create pseudo-words made up for randomly chosen digits and add them as the first and last words.
+    /// 3. Create pseudo-words made up for randomly chosen digits and add them as the first and last words,
+    ///    or elsewhere among the words depending on the [`DigitPlacement`].
     /// 4. Insert a copy of the same symbol between each of the words and pseudo-words. This symbol is referred to as the separator character.
+    ///    Depending on the [`SeparatorType`], each gap may instead get its own symbol, or the next one from a fixed sequence.
     /// 5. Pad the password with multiple instances of the same symbol front and/or back. This symbol is referred to as the padding character.
-    pub fn make_password(&mut self) -> String {
-        let filtered_word_indices = self.filter_wordlist();
-        let chosen_words = self.choose_words(&filtered_word_indices);
-        let mut transformed_words = self.transform_words(chosen_words);
-        let (front_digits, back_digits) = self.create_pseudo_words();
-        let separator = self.choose_separator();
+    ///    Depending on the [`PaddingFill`], each end may instead get its own symbol, or a random sequence of them.
+    /// 6. If the password breaks the configured [`Policy`](crate::policy::Policy), throw it away and start over.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PolicyError`] if [`MAX_ATTEMPTS`] passwords in a row break the policy,
+    /// or if no words fit within the maximum length.
+    pub fn make_password(&mut self) -> Result<SecretString, PolicyError> {
+        let slots = self.slots();
+        for _ in 0..MAX_ATTEMPTS {
+            let password = self.make_candidate(&slots).ok_or(PolicyError)?;
+            if self.config.policy.is_satisfied_by(&password) {
+                return Ok(password);
+            }
+        }
+        Err(PolicyError)
+    }
+    /// Create a password from words drawn from `slots`,
+    /// without regard for the policy beyond fitting the words within its maximum length.
+    ///
+    /// Returns [`None`] if the words do not fit.
+    fn make_candidate(&mut self, slots: &[Slot]) -> Option<SecretString> {
+        let chosen_words = self.choose_words(slots)?;
+        let transformed_words = self.transform_words(chosen_words);
+        let parts = self.arrange_parts(transformed_words);
 
         // begin constructing the password sans padding
-        let mut parts = vec![front_digits.unwrap_or(String::new())];
-        parts.append(&mut transformed_words);
-        parts.push(back_digits.unwrap_or(String::new()));
-        // TODO regression test for: separator should not apply on empty elements
-        let unpadded_password = parts
-            .into_iter()
-            .filter(|p| !p.is_empty())
-            .collect::<Vec<String>>()
-            .join(&separator.map(String::from).unwrap_or_default());
+        let separators = self.choose_separators(parts.len().saturating_sub(1));
+        let capacity = parts.iter().map(|p| p.len()).sum::<usize>() + separators.len();
+        let mut unpadded_password = SecretString::with_capacity(capacity);
+        let mut separators = separators.chars();
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0
+                && let Some(separator) = separators.next()
+            {
+                unpadded_password.push(separator);
+            }
+            unpadded_password.push_str(part);
+        }
 
         let (front_padding, rear_padding) = self.create_padding(&unpadded_password);
+        let front_padding = front_padding.unwrap_or_default();
+        let rear_padding = rear_padding.unwrap_or_default();
 
-        [
-            front_padding.unwrap_or(String::new()),
-            unpadded_password,
-            rear_padding.unwrap_or(String::new()),
-        ]
-        .join("")
+        let mut password = SecretString::with_capacity(
+            front_padding.len() + unpadded_password.len() + rear_padding.len(),
+        );
+        password.push_str(&front_padding);
+        password.push_str(&unpadded_password);
+        password.push_str(&rear_padding);
+        Some(password)
     }
     /// Create passwords.
     /// This is the public interface for the [`PasswordMaker`] struct.
-    pub fn make_passwords(&mut self) -> Vec<String> {
+    ///
+    /// # Errors
+    ///
+    /// Will return [`PolicyError`] if any password can not be made, see [`Self::make_password`].
+    pub fn make_passwords(&mut self) -> Result<Vec<SecretString>, PolicyError> {
         let count = self.config.count as usize;
         let mut buf = Vec::with_capacity(count);
         for _ in 0..count {
-            buf.push(self.make_password());
+            buf.push(self.make_password()?);
         }
-        buf
+        Ok(buf)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::policy::Policy;
     use crate::test_helpers::*;
+    use crate::types::JoinedWords;
+
+    /// The filtered indices of `maker`'s own wordlist.
+    fn own_indices<T: TryRngCore>(maker: &mut PasswordMaker<T>) -> Arc<[u32]> {
+        let wordlist = Arc::clone(&maker.wordlist);
+        maker.filtered_word_indices(&wordlist)
+    }
+
+    /// A slot for each of `maker`'s words, drawing `indices` from its own wordlist.
+    fn slots<T: TryRngCore>(maker: &PasswordMaker<T>, indices: &[u32]) -> Vec<Slot> {
+        let slot = Slot {
+            parts: vec![SlotPart {
+                wordlist: Arc::clone(&maker.wordlist),
+                indices: indices.into(),
+                weight: 1,
+            }],
+        };
+        vec![slot; maker.config.word_count as usize]
+    }
 
     const PADDING_TYPES: [PaddingType; 7] = [
         PaddingType::FixedFront,
@@ -265,10 +839,9 @@ mod tests {
     fn test_filter_wordlist() {
         // some test parametrization wouldn't go amiss here.
         let mut maker = make_seeded_maker(1);
-        maker.wordlist = make_wordlist();
         maker.config.word_min_length = 4;
         maker.config.word_max_length = 4;
-        let result = maker.filter_wordlist();
+        let result = maker.filter_wordlist(&maker.wordlist);
         let expected = [5, 6];
         let matches = result
             .iter()
@@ -279,6 +852,33 @@ mod tests {
         assert_eq!(expected.len(), matches, "expected.len() == matches");
     }
 
+    #[test]
+    fn test_filtered_word_indices_cache() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.word_min_length = 4;
+        maker.config.word_max_length = 4;
+        let first = own_indices(&mut maker);
+        assert!(Arc::ptr_eq(&first, &own_indices(&mut maker)));
+        maker.config.word_max_length = 5;
+        let second = own_indices(&mut maker);
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(second.len(), 6);
+        maker.wordlist = make_wordlist()
+            .into_iter()
+            .map(String::into_boxed_str)
+            .collect();
+        let third = own_indices(&mut maker);
+        assert!(!Arc::ptr_eq(&second, &third));
+        maker.config.avoid_ambiguous = true;
+        let fourth = own_indices(&mut maker);
+        assert!(!Arc::ptr_eq(&third, &fourth));
+        maker.config.easy_typing = true;
+        let fifth = own_indices(&mut maker);
+        assert!(!Arc::ptr_eq(&fourth, &fifth));
+        maker.config.keyboard = KeyboardLayout::Mobile;
+        assert!(!Arc::ptr_eq(&fifth, &own_indices(&mut maker)));
+    }
+
     /// `choose_words` should be choosing with replacement,
     /// if `config.word_count > config.wordlist.len()`,
     /// do not panic and ensure the final length is == the configured word count.
@@ -289,8 +889,8 @@ mod tests {
         for param in params {
             let mut maker = make_seeded_maker(1);
             maker.config.word_count = param;
-            let indices: [u32; 2] = [1, 2];
-            let result = maker.choose_words(&indices);
+            let slots = slots(&maker, &[1, 2]);
+            let result = maker.choose_words(&slots).unwrap();
             assert_eq!(result.len(), param as usize);
         }
     }
@@ -304,8 +904,8 @@ mod tests {
         for i in 0..2 {
             let mut maker = make_seeded_maker(seeds[i]);
             maker.config.word_count = 2;
-            let indices: [u32; 2] = [1, 2];
-            let result = maker.choose_words(&indices);
+            let slots = slots(&maker, &[1, 2]);
+            let result = maker.choose_words(&slots).unwrap();
             assert_eq!(result[0], expected[i].0);
             assert_eq!(result[1], expected[i].1);
         }
@@ -322,7 +922,7 @@ mod tests {
     fn test_transform_words_none() {
         let mut maker = make_seeded_maker(1);
         maker.config.word_transformation = WordTransformationType::None;
-        let v = vec!["abCD".to_owned()];
+        let v = secrets(&["abCD"]);
         assert_eq!(v, maker.transform_words(v.clone()));
     }
 
@@ -339,6 +939,15 @@ mod tests {
         assert_eq!("871".to_owned(), result);
     }
 
+    #[test]
+    fn test_choose_n_digits_custom() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.digit_characters = vec!['x', 'y'];
+        let result = maker.choose_n_digits(20).unwrap();
+        assert!(result.chars().all(|c| c == 'x' || c == 'y'), "{result:?}");
+        maker.config.digit_characters = Vec::new();
+        assert!(maker.choose_n_digits(3).is_none());
+    }
     #[test]
     fn test_create_pseudo_words_ok() {
         let mut maker = make_seeded_maker(1);
@@ -369,17 +978,93 @@ mod tests {
         assert!(right.is_none());
     }
     #[test]
-    fn test_choose_separator_default() {
+    fn test_arrange_parts_ends() {
         let mut maker = make_seeded_maker(1);
-        let result = maker.choose_separator().unwrap();
-        assert_eq!(result, '?');
+        maker.config.digits_before = 1;
+        let parts = maker.arrange_parts(secrets(&["a", "b"]));
+        assert_eq!(parts.len(), 4);
+        assert!(parts[0].chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(parts[1..3], secrets(&["a", "b"]));
+        assert!(parts[3].chars().all(|c| c.is_ascii_digit()));
     }
     #[test]
-    fn test_choose_separator_empty() {
+    fn test_arrange_parts_random_gap() {
         let mut maker = make_seeded_maker(1);
-        maker.config.separator_characters = Vec::new();
-        let result = maker.choose_separator();
-        assert!(result.is_none());
+        maker.config.digit_placement = DigitPlacement::RandomGap;
+        let mut gaps = [0; 3];
+        for _ in 0..100 {
+            let parts = maker.arrange_parts(secrets(&["a", "b"]));
+            assert_eq!(parts.len(), 3);
+            let gap = parts.iter().position(|p| p.len() == 2).unwrap();
+            gaps[gap] += 1;
+            let words = parts.iter().filter(|p| p.len() == 1).collect::<Vec<_>>();
+            assert_eq!(words, ["a", "b"]);
+        }
+        assert!(gaps.iter().all(|n| *n > 0), "{gaps:?}");
+    }
+    #[test]
+    fn test_arrange_parts_attached() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.digits_before = 1;
+        maker.config.digit_placement = DigitPlacement::Attached;
+        for _ in 0..20 {
+            let parts = maker.arrange_parts(secrets(&["a", "b"]));
+            assert_eq!(parts.len(), 2);
+            assert_eq!(parts.iter().map(|p| p.len()).sum::<usize>(), 5);
+            for (part, word) in parts.iter().zip(["a", "b"]) {
+                assert_eq!(part.trim_matches(|c: char| c.is_ascii_digit()), word);
+            }
+        }
+        // with no words, the digits go at the ends
+        assert_eq!(maker.arrange_parts(Vec::new()).len(), 2);
+    }
+    #[test]
+    fn test_arrange_parts_every_gap() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.digits_before = 1;
+        maker.config.digit_placement = DigitPlacement::EveryGap;
+        let parts = maker.arrange_parts(secrets(&["a", "b"]));
+        let lengths = parts.iter().map(|p| p.len()).collect::<Vec<_>>();
+        assert_eq!(lengths, [1, 1, 2, 1, 2]);
+    }
+    #[test]
+    fn test_choose_separators_default() {
+        let mut maker = make_seeded_maker(1);
+        let result = maker.choose_separators(3);
+        assert_eq!(result, "???");
+    }
+    #[test]
+    fn test_choose_separators_empty() {
+        for separator_type in [
+            SeparatorType::Single,
+            SeparatorType::PerGap,
+            SeparatorType::Sequence,
+        ] {
+            let mut maker = make_seeded_maker(1);
+            maker.config.separator_characters = Vec::new();
+            maker.config.separator_type = separator_type;
+            assert!(maker.choose_separators(3).is_empty());
+        }
+    }
+    #[test]
+    fn test_choose_separators_per_gap() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.separator_type = SeparatorType::PerGap;
+        let result = maker.choose_separators(20);
+        assert_eq!(result.chars().count(), 20);
+        assert!(result.chars().any(|c| !result.starts_with(c)), "{result:?}");
+    }
+    #[test]
+    fn test_choose_separators_sequence() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.separator_type = SeparatorType::Sequence;
+        maker.config.separator_sequence = vec!['-', '.', '-', '_'];
+        assert_eq!(maker.choose_separators(6), "-.-_-.");
+        maker.config.word_count = 2;
+        maker.config.padding_type = PaddingType::None;
+        let password = maker.make_password().unwrap();
+        assert_eq!(password.matches('-').count(), 1, "{password:?}");
+        assert_eq!(password.matches('.').count(), 1, "{password:?}");
     }
     #[test]
     fn test_create_padding_none() {
@@ -480,24 +1165,294 @@ mod tests {
         assert_eq!("?????", &right.unwrap());
     }
     #[test]
+    fn test_create_padding_independent() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.padding_type = PaddingType::FixedBoth;
+        maker.config.padding_length = 3;
+        maker.config.padding_fill = PaddingFill::Independent;
+        maker.config.padding_front_characters = vec!['<'];
+        maker.config.padding_back_characters = vec!['>'];
+        let (left, right) = maker.create_padding("");
+        assert_eq!("<<<", &left.unwrap());
+        assert_eq!(">>>", &right.unwrap());
+        maker.config.padding_back_characters = Vec::new();
+        let (left, right) = maker.create_padding("");
+        assert_eq!("<<<", &left.unwrap());
+        assert!(right.is_none());
+    }
+    #[test]
+    fn test_create_padding_random() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.padding_type = PaddingType::AdaptiveFront;
+        maker.config.padding_length = 25;
+        maker.config.padding_fill = PaddingFill::Random;
+        maker.config.padding_front_characters = vec!['a', 'b'];
+        let (left, right) = maker.create_padding("Hello");
+        let left = left.unwrap();
+        assert_eq!(left.len(), 20);
+        assert!(left.contains('a') && left.contains('b'), "{left:?}");
+        assert!(right.is_none());
+    }
+    #[test]
     fn test_make_password_default() {
         let mut maker = make_seeded_maker_big_list(1);
-        let password = maker.make_password();
+        let password = maker.make_password().unwrap();
         assert_eq!("startling;SHAFT;cactus;SHACK;15+", &password);
     }
     #[test]
     fn test_make_passwords_default() {
         let mut maker = make_seeded_maker_big_list(1);
-        let passwords = maker.make_passwords();
+        let passwords = maker.make_passwords().unwrap();
         assert_eq!("startling;SHAFT;cactus;SHACK;15+", &passwords[0]);
     }
     #[test]
     fn test_create_3_passwords() {
         let mut maker = make_seeded_maker_big_list(1);
         maker.config.count = 3;
-        let passwords = maker.make_passwords();
+        let passwords = maker.make_passwords().unwrap();
         assert_eq!("startling;SHAFT;cactus;SHACK;15+", &passwords[0]);
         assert_eq!("bullwhip@CHUNK@uniquely@FOOTBALL@03$", &passwords[1]);
         assert_eq!("overarch$LETDOWN$valid$PUSHY$27-", &passwords[2]);
     }
+    #[test]
+    fn test_make_password_policy_rejects() {
+        let mut maker = make_seeded_maker_big_list(1);
+        maker.config.policy.min_uppercase = 11;
+        // the first candidate has 10 uppercase characters, and so is replaced with the second
+        assert_eq!(
+            "bullwhip@CHUNK@uniquely@FOOTBALL@03$",
+            &maker.make_password().unwrap()
+        );
+    }
+    #[test]
+    fn test_make_password_policy_satisfied() {
+        let mut maker = make_seeded_maker_big_list(1);
+        maker.config.policy = Policy {
+            min_digits: 2,
+            max_length: 28,
+            max_consecutive: 2,
+            ..Policy::default()
+        };
+        for _ in 0..100 {
+            let password = maker.make_password().unwrap();
+            assert!(
+                maker.config.policy.is_satisfied_by(&password),
+                "{password:?}"
+            );
+        }
+    }
+    #[test]
+    fn test_make_password_policy_impossible() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.policy.min_digits = 3;
+        assert_eq!(maker.make_password().unwrap_err(), PolicyError);
+        assert_eq!(maker.make_passwords().unwrap_err(), PolicyError);
+    }
+    #[test]
+    fn test_choose_words_fit_max_length() {
+        let mut maker = make_seeded_maker_big_list(1);
+        // 2 digits, 4 separators and 1 padding leave 17 characters for 4 words
+        maker.config.policy.max_length = 24;
+        let indices = own_indices(&mut maker);
+        for _ in 0..100 {
+            let words = maker.choose_words(&slots(&maker, &indices)).unwrap();
+            assert_eq!(words.len(), 4);
+            assert!(words.iter().map(|w| w.chars().count()).sum::<usize>() <= 17);
+        }
+    }
+    #[test]
+    fn test_choose_words_do_not_fit() {
+        let mut maker = make_seeded_maker(1);
+        // 8 characters are left for 4 words of at least 3
+        maker.config.policy.max_length = 15;
+        let indices = own_indices(&mut maker);
+        assert!(maker.choose_words(&slots(&maker, &indices)).is_none());
+        assert_eq!(maker.make_password().unwrap_err(), PolicyError);
+    }
+
+    #[test]
+    fn test_make_password_just_too_long() {
+        let mut maker = make_seeded_maker_big_list(1);
+        // 2 digits, 4 separators and 1 padding leave 9 characters for 4 words of at least 3
+        maker.config.policy.max_length = 16;
+        assert_eq!(maker.make_password().unwrap_err(), PolicyError);
+        assert_eq!(maker.make_passwords().unwrap_err(), PolicyError);
+    }
+    #[test]
+    fn test_filter_wordlist_excluded_words() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.excluded_words = vec![Box::from("hello"), Box::from("world")];
+        let indices = maker.filter_wordlist(&maker.wordlist);
+        assert_eq!(indices.len(), make_wordlist().len() - 2);
+        assert!(
+            indices
+                .iter()
+                .all(|&i| !["hello", "world"].contains(&&*maker.wordlist[i as usize]))
+        );
+    }
+
+    #[test]
+    fn test_filter_wordlist_prefix_free() {
+        let mut maker = make_seeded_maker(1);
+        maker.wordlist = ["inside", "in", "ways", "sideways", "side"]
+            .into_iter()
+            .map(Box::from)
+            .collect();
+        maker.config.word_min_length = 2;
+        maker.config.separator_characters = Vec::new();
+        maker.config.word_transformation = WordTransformationType::Lower;
+        assert_eq!(own_indices(&mut maker).len(), 5);
+        maker.config.joined_words = JoinedWords::PrefixFree;
+        let indices = own_indices(&mut maker);
+        assert_eq!(&*indices, [0, 2, 3]);
+        maker.config.separator_characters = vec!['-'];
+        assert_eq!(own_indices(&mut maker).len(), 5);
+    }
+
+    #[test]
+    fn test_choose_words_avoid_blocked_joins() {
+        let mut maker = make_seeded_maker(1);
+        maker.wordlist = ["push", "it"].into_iter().map(Box::from).collect();
+        maker.config.word_count = 2;
+        maker.config.word_min_length = 2;
+        maker.config.blocklist = true;
+        let indices = own_indices(&mut maker);
+        assert_eq!(indices.len(), 2);
+        for _ in 0..100 {
+            let words = maker.choose_words(&slots(&maker, &indices)).unwrap();
+            assert!(!(words[0] == "push" && words[1] == "it"));
+        }
+    }
+
+    #[test]
+    fn test_part_of_speech_wordlists() {
+        let wordlists = PartOfSpeech::NAME_MEMBER_ARR
+            .iter()
+            .map(|(_, part)| part_of_speech_wordlist(*part))
+            .collect::<Vec<_>>();
+        let mut words = Vec::new();
+        for wordlist in &wordlists {
+            assert!(!wordlist.is_empty());
+            assert!(
+                wordlist
+                    .iter()
+                    .all(|word| default_wordlist().contains(word))
+            );
+            words.extend(wordlist.iter());
+        }
+        let count = words.len();
+        words.sort_unstable();
+        words.dedup();
+        assert_eq!(words.len(), count, "each word is one part of speech");
+    }
+
+    #[test]
+    fn test_make_password_template() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.template = vec![PartOfSpeech::Adj, PartOfSpeech::Noun, PartOfSpeech::Verb];
+        maker.config.word_count = 3;
+        maker.config.word_transformation = WordTransformationType::Lower;
+        maker.config.word_max_length = 9;
+        let slots = maker.slots();
+        assert_eq!(slots.len(), 3);
+        for _ in 0..20 {
+            let words = maker.choose_words(&slots).unwrap();
+            for (word, part) in words.iter().zip(&maker.config.template) {
+                assert!(
+                    part_of_speech_wordlist(*part)
+                        .iter()
+                        .any(|w| &**w == word.as_str())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_make_password_slot_wordlists() {
+        let mut maker = make_seeded_maker(1);
+        maker.config = ConfigBuilder::new()
+            .custom_wordlist(Some("kafka\nredis\n".to_owned()))
+            .slot_wordlists(Some("custom adj".to_owned()))
+            .word_transformation(Some("lower".to_owned()))
+            .build()
+            .unwrap();
+        let slots = maker.slots();
+        assert_eq!(slots.len(), 2);
+        for _ in 0..20 {
+            let words = maker.choose_words(&slots).unwrap();
+            assert!(["kafka", "redis"].contains(&words[0].as_str()));
+            assert!(
+                part_of_speech_wordlist(PartOfSpeech::Adj)
+                    .iter()
+                    .any(|w| &**w == words[1].as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn test_choose_candidate_weights() {
+        let mut maker = make_seeded_maker(1);
+        let candidates = [
+            (3, Arc::from([0])),
+            (0, Arc::from([1])),
+            (1, Arc::from([2])),
+        ];
+        let mut counts = [0; 3];
+        for _ in 0..4000 {
+            let (part, i) = maker.choose_candidate(&candidates);
+            assert_eq!(part, i as usize);
+            counts[part] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!((2800..3200).contains(&counts[0]), "{counts:?}");
+    }
+
+    /// Leaving out words which can not fit leaves the others as likely as each other,
+    /// as if they had been drawn and thrown away.
+    #[test]
+    fn test_choose_words_fit_max_length_mixed() {
+        let mut maker = make_seeded_maker(1);
+        maker.config.word_count = 1;
+        maker.config.digits_after = 0;
+        maker.config.padding_type = PaddingType::None;
+        maker.config.policy.max_length = 4;
+        let part = |words: &[&str], indices: &[u32]| SlotPart {
+            wordlist: words.iter().copied().map(Box::from).collect(),
+            indices: indices.into(),
+            weight: 1,
+        };
+        let slots = [Slot {
+            parts: vec![part(&["toolong", "fits"], &[0, 1]), part(&["nice"], &[0])],
+        }];
+        let mut fits = 0;
+        for _ in 0..3000 {
+            let words = maker.choose_words(&slots).unwrap();
+            assert_ne!(words[0].as_str(), "toolong");
+            if words[0].as_str() == "fits" {
+                fits += 1;
+            }
+        }
+        assert!((1400..1600).contains(&fits), "{fits}");
+    }
+
+    #[test]
+    fn test_builtin_wordlist() {
+        let name = builtin_wordlist_names()[0];
+        let wordlist = builtin_wordlist(name).unwrap();
+        assert!(Arc::ptr_eq(&wordlist, &default_wordlist()));
+        assert_eq!(
+            builtin_wordlists().next(),
+            Some((name, WORDLISTS[0].1, wordlist.len()))
+        );
+        assert!(builtin_wordlist("klingon").is_none());
+    }
+    #[test]
+    fn test_builtin_wordlists_load() {
+        for (name, _, len) in builtin_wordlists() {
+            let wordlist = builtin_wordlist(name).unwrap();
+            assert_eq!(wordlist.len(), len, "{name}");
+            assert!(!wordlist.is_empty(), "{name}");
+            assert!(wordlist.iter().all(|word| !word.is_empty()), "{name}");
+        }
+    }
 }
@@ -0,0 +1,447 @@
+//! Password policies, as imposed by the systems passwords are made for.
+//!
+//! [`PasswordMaker`] enforces a [`Policy`] by rejection sampling:
+//! passwords which break it are thrown away and replaced with fresh ones.
+//! Every compliant password stays exactly as likely as any other, so no bias is introduced,
+//! but the number of possible passwords shrinks.
+//!
+//! The maximum length is also met ahead of time, by only choosing words which fit together
+//! within [`Policy::word_length_budget`]. That narrows the choice in exactly the same way.
+//!
+//! [`PasswordMaker`]: crate::password_maker::PasswordMaker
+use std::fmt;
+
+use crate::config::Config;
+use crate::types::PaddingFill;
+use crate::types::PaddingType;
+use crate::types::WordTransformationType;
+
+/// How many passwords [`PasswordMaker`] may reject before giving up on a [`Policy`].
+///
+/// [`PasswordMaker`]: crate::password_maker::PasswordMaker
+pub const MAX_ATTEMPTS: u32 = 10_000;
+
+/// Rules a password must follow.
+///
+/// A value of 0 means the rule does not apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Policy {
+    /// minimum number of uppercase characters
+    pub min_uppercase: u8,
+    /// minimum number of lowercase characters
+    pub min_lowercase: u8,
+    /// minimum number of digits
+    pub min_digits: u8,
+    /// minimum number of symbols, which are any characters that are not alphanumeric
+    pub min_symbols: u8,
+    /// maximum length in characters
+    pub max_length: u8,
+    /// maximum number of identical consecutive characters
+    pub max_consecutive: u8,
+}
+
+/// Count the characters of `password` which satisfy `f`.
+fn count(password: &str, f: impl Fn(&char) -> bool) -> usize {
+    password.chars().filter(f).count()
+}
+
+/// The length of the longest run of identical characters in `password`.
+fn longest_run(password: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous = None;
+    for c in password.chars() {
+        current = if previous == Some(c) { current + 1 } else { 1 };
+        longest = longest.max(current);
+        previous = Some(c);
+    }
+    longest
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_alphanumeric()
+}
+
+/// The number of separators between the parts of a password made with `config`.
+fn separator_count(config: &Config) -> usize {
+    if config.separator_alphabet().is_empty() {
+        0
+    } else {
+        config
+            .part_count(config.word_count as usize)
+            .saturating_sub(1)
+    }
+}
+
+/// The length of the padding that is always added, regardless of the rest of the password.
+const fn fixed_padding_length(config: &Config) -> usize {
+    let padding_length = config.padding_length as usize;
+    match config.padding_type {
+        PaddingType::FixedFront | PaddingType::FixedBack => padding_length,
+        PaddingType::FixedBoth | PaddingType::Fixed => 2 * padding_length,
+        PaddingType::None
+        | PaddingType::AdaptiveFront
+        | PaddingType::AdaptiveBack
+        | PaddingType::Adaptive => 0,
+    }
+}
+
+impl Policy {
+    /// Whether there are any rules at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+    /// Check whether `password` follows every rule.
+    #[must_use]
+    pub fn is_satisfied_by(&self, password: &str) -> bool {
+        count(password, |c| c.is_uppercase()) >= self.min_uppercase as usize
+            && count(password, |c| c.is_lowercase()) >= self.min_lowercase as usize
+            && count(password, char::is_ascii_digit) >= self.min_digits as usize
+            && count(password, |c| is_symbol(*c)) >= self.min_symbols as usize
+            && (self.max_length == 0 || password.chars().count() <= self.max_length as usize)
+            && (self.max_consecutive == 0 || longest_run(password) <= self.max_consecutive as usize)
+    }
+    /// The number of characters left for the words of a password made with `config`,
+    /// once digits, separators and fixed padding have taken their share of the maximum length.
+    ///
+    /// Returns [`None`] when there is no maximum length.
+    /// Adaptive padding only ever fills the password up, so it takes no share.
+    #[must_use]
+    pub fn word_length_budget(&self, config: &Config) -> Option<usize> {
+        if self.max_length == 0 {
+            return None;
+        }
+        let digit_count = config.digit_count(config.word_count as usize);
+        Some(
+            (self.max_length as usize).saturating_sub(
+                digit_count + separator_count(config) + fixed_padding_length(config),
+            ),
+        )
+    }
+    /// Find the rules which `config` can never, or will always, break.
+    ///
+    /// This errs on the side of silence: an empty result does not promise that the policy can be met.
+    /// Words are assumed to be lowercase and free of digits and symbols, as in the default wordlist.
+    #[must_use]
+    pub fn conflicts(&self, config: &Config) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        let word_count = config.word_count as usize;
+        let digit_count = config.digit_count(word_count);
+        let separator_count = separator_count(config);
+        let padding_length = config.padding_length as usize;
+        let fixed_padding = fixed_padding_length(config);
+        // (longest run of one character, most that can be added)
+        let (repeated_run, max_padding) = match config.padding_type {
+            PaddingType::None => (0, 0),
+            PaddingType::FixedFront | PaddingType::FixedBack => (padding_length, padding_length),
+            PaddingType::FixedBoth | PaddingType::Fixed => (padding_length, 2 * padding_length),
+            PaddingType::AdaptiveFront | PaddingType::AdaptiveBack | PaddingType::Adaptive => {
+                (0, padding_length)
+            }
+        };
+        // a random sequence only repeats a character by chance
+        let padding_run = if config.padding_fill == PaddingFill::Random {
+            0
+        } else {
+            repeated_run
+        };
+        // separators and padding may be any character, not only symbols
+        let [front_padding, back_padding] = config.padding_alphabets();
+        let padding_chars = front_padding.iter().chain(back_padding);
+        let other_chars = config
+            .separator_alphabet()
+            .iter()
+            .chain(padding_chars.clone());
+        let any_other_char = |f: fn(&char) -> bool| other_chars.clone().any(f);
+        // digits may be drawn from any characters too
+        let any_digit_char =
+            |f: fn(&char) -> bool| digit_count > 0 && config.digit_characters.iter().any(f);
+
+        let words_have_uppercase = word_count > 0
+            && !matches!(
+                config.word_transformation,
+                WordTransformationType::None | WordTransformationType::Lower
+            );
+        let is_uppercase = |c: &char| c.is_uppercase();
+        if self.min_uppercase > 0
+            && !words_have_uppercase
+            && !any_other_char(is_uppercase)
+            && !any_digit_char(is_uppercase)
+        {
+            conflicts.push(Conflict::NoUppercase);
+        }
+        let words_have_lowercase =
+            word_count > 0 && config.word_transformation != WordTransformationType::Upper;
+        let is_lowercase = |c: &char| c.is_lowercase();
+        if self.min_lowercase > 0
+            && !words_have_lowercase
+            && !any_other_char(is_lowercase)
+            && !any_digit_char(is_lowercase)
+        {
+            conflicts.push(Conflict::NoLowercase);
+        }
+        let max_digits = if any_digit_char(char::is_ascii_digit) {
+            digit_count
+        } else {
+            0
+        };
+        if self.min_digits as usize > max_digits && !any_other_char(char::is_ascii_digit) {
+            conflicts.push(Conflict::TooFewDigits(max_digits));
+        }
+        let max_symbols = if config.separator_alphabet().iter().any(|c| is_symbol(*c)) {
+            separator_count
+        } else {
+            0
+        } + if padding_chars.clone().any(|c| is_symbol(*c)) {
+            max_padding
+        } else {
+            0
+        } + if any_digit_char(|c| is_symbol(*c)) {
+            digit_count
+        } else {
+            0
+        };
+        if self.min_symbols as usize > max_symbols {
+            conflicts.push(Conflict::TooFewSymbols(max_symbols));
+        }
+        if self.max_length > 0 {
+            let min_length = (word_count * config.word_min_length as usize
+                + digit_count
+                + separator_count
+                + fixed_padding)
+                .max(max_padding);
+            if min_length > self.max_length as usize {
+                conflicts.push(Conflict::TooLong(min_length));
+            }
+        }
+        if self.max_consecutive > 0 && padding_run > self.max_consecutive as usize {
+            conflicts.push(Conflict::PaddingTooRepetitive(padding_run));
+        }
+        conflicts
+    }
+}
+
+/// A [`Policy`] rule which a [`Config`] can not satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// Nothing can be uppercase.
+    NoUppercase,
+    /// Nothing can be lowercase.
+    NoLowercase,
+    /// At most this many digits can be present.
+    TooFewDigits(usize),
+    /// At most this many symbols can be present.
+    TooFewSymbols(usize),
+    /// Passwords are at least this long.
+    TooLong(usize),
+    /// Padding always repeats a character this many times.
+    PaddingTooRepetitive(usize),
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoUppercase => write!(
+                f,
+                "uppercase characters are required, but the word transformation never makes any"
+            ),
+            Self::NoLowercase => write!(
+                f,
+                "lowercase characters are required, but the word transformation never makes any"
+            ),
+            Self::TooFewDigits(n) => write!(
+                f,
+                "more digits are required than the {n} the config provides"
+            ),
+            Self::TooFewSymbols(n) => write!(
+                f,
+                "more symbols are required than the {n} the config can provide"
+            ),
+            Self::TooLong(n) => write!(
+                f,
+                "passwords are at least {n} characters long, which exceeds the maximum length"
+            ),
+            Self::PaddingTooRepetitive(n) => write!(
+                f,
+                "padding repeats a character {n} times, which exceeds the maximum consecutive characters"
+            ),
+        }
+    }
+}
+
+/// [`PasswordMaker`] gave up on finding a password which satisfies the [`Policy`].
+///
+/// [`PasswordMaker`]: crate::password_maker::PasswordMaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyError;
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no password satisfying the policy was found in {MAX_ATTEMPTS} attempts"
+        )
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[test]
+    fn test_empty_policy_accepts_anything() {
+        assert!(Policy::default().is_empty());
+        for password in ["", "a", "aaaaaaaa", "!!!"] {
+            assert!(Policy::default().is_satisfied_by(password));
+        }
+    }
+
+    #[test]
+    fn test_is_satisfied_by() {
+        let policy = Policy {
+            min_uppercase: 1,
+            min_lowercase: 1,
+            min_digits: 1,
+            min_symbols: 1,
+            max_length: 8,
+            max_consecutive: 2,
+        };
+        assert!(policy.is_satisfied_by("Ab1!"));
+        assert!(policy.is_satisfied_by("AAbb11!!"));
+        assert!(!policy.is_satisfied_by("ab1!"));
+        assert!(!policy.is_satisfied_by("AB1!"));
+        assert!(!policy.is_satisfied_by("Abc!"));
+        assert!(!policy.is_satisfied_by("Abc1"));
+        assert!(!policy.is_satisfied_by("Ab1!xxyyz"));
+        assert!(!policy.is_satisfied_by("Abbb1!"));
+    }
+
+    #[test]
+    fn test_longest_run() {
+        assert_eq!(longest_run(""), 0);
+        assert_eq!(longest_run("abc"), 1);
+        assert_eq!(longest_run("abbcccb"), 3);
+    }
+
+    #[test]
+    fn test_no_conflicts_by_default() {
+        let policy = Policy {
+            min_uppercase: 1,
+            min_lowercase: 1,
+            min_digits: 2,
+            min_symbols: 1,
+            max_length: 64,
+            max_consecutive: 2,
+        };
+        assert!(policy.conflicts(&Config::default()).is_empty());
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let config = ConfigBuilder::new()
+            .word_transformation(Some("lower".to_owned()))
+            .padding_type(Some("fixed-both".to_owned()))
+            .padding_length(Some("3".to_owned()))
+            .build()
+            .unwrap();
+        let policy = Policy {
+            min_uppercase: 1,
+            min_digits: 3,
+            min_symbols: 11,
+            max_length: 12,
+            max_consecutive: 2,
+            ..Policy::default()
+        };
+        assert_eq!(
+            policy.conflicts(&config),
+            [
+                Conflict::NoUppercase,
+                Conflict::TooFewDigits(2),
+                Conflict::TooFewSymbols(10),
+                // 4 words of at least 3, 2 digits, 4 separators and 6 padding
+                Conflict::TooLong(24),
+                Conflict::PaddingTooRepetitive(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_length_budget() {
+        assert_eq!(
+            Policy::default().word_length_budget(&Config::default()),
+            None
+        );
+        let policy = Policy {
+            max_length: 30,
+            ..Policy::default()
+        };
+        // 2 digits, 4 separators and 1 padding
+        assert_eq!(policy.word_length_budget(&Config::default()), Some(23));
+        let config = ConfigBuilder::new()
+            .padding_type(Some("adaptive".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(policy.word_length_budget(&config), Some(24));
+        let policy = Policy {
+            max_length: 6,
+            ..Policy::default()
+        };
+        assert_eq!(policy.word_length_budget(&Config::default()), Some(0));
+    }
+
+    #[test]
+    fn test_adaptive_padding_conflicts() {
+        let config = ConfigBuilder::new()
+            .padding_type(Some("adaptive".to_owned()))
+            .build()
+            .unwrap();
+        let policy = Policy {
+            max_length: 20,
+            ..Policy::default()
+        };
+        assert_eq!(policy.conflicts(&config), [Conflict::TooLong(42)]);
+    }
+
+    #[test]
+    fn test_digit_class_conflicts() {
+        let policy = Policy {
+            min_digits: 1,
+            ..Policy::default()
+        };
+        let config = ConfigBuilder::new()
+            .word_transformation(Some("lower".to_owned()))
+            .digit_class(Some("upper".to_owned()))
+            .build()
+            .unwrap();
+        assert_eq!(policy.conflicts(&config), [Conflict::TooFewDigits(0)]);
+        let policy = Policy {
+            min_uppercase: 1,
+            ..Policy::default()
+        };
+        assert!(policy.conflicts(&config).is_empty());
+    }
+
+    #[test]
+    fn test_random_padding_is_not_repetitive() {
+        let make = |fill: &str| {
+            ConfigBuilder::new()
+                .padding_length(Some("3".to_owned()))
+                .padding_fill(Some(fill.to_owned()))
+                .build()
+                .unwrap()
+        };
+        let policy = Policy {
+            max_consecutive: 2,
+            ..Policy::default()
+        };
+        assert_eq!(
+            policy.conflicts(&make("independent")),
+            [Conflict::PaddingTooRepetitive(3)]
+        );
+        assert!(policy.conflicts(&make("random")).is_empty());
+    }
+}
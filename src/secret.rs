@@ -0,0 +1,201 @@
+//! Provides the [`SecretString`] type.
+//!
+//! Passwords and the pieces they are built from should not outlive their usefulness
+//! in freed heap memory, so they are kept in buffers that are zeroed before being released.
+use std::fmt;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use zeroize::Zeroize;
+
+/// A [`String`] which zeroes its buffer when dropped or when it has to grow.
+///
+/// With the `mlock` feature enabled, the buffer is also locked into memory on a best effort basis
+/// so that it is not written out to swap.
+/// Locking is page granular and not reference counted,
+/// so dropping one [`SecretString`] may unlock a page shared with another.
+#[derive(Default)]
+pub struct SecretString {
+    inner: String,
+    #[cfg(feature = "mlock")]
+    _lock: Option<region::LockGuard>,
+}
+
+impl SecretString {
+    #[must_use]
+    /// Create an empty [`SecretString`] without allocating.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[must_use]
+    /// Create an empty [`SecretString`] that can hold `capacity` bytes before it needs to grow.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let inner = String::with_capacity(capacity);
+        #[cfg(feature = "mlock")]
+        let lock = if inner.capacity() == 0 {
+            None
+        } else {
+            region::lock(inner.as_ptr(), inner.capacity()).ok()
+        };
+        Self {
+            inner,
+            #[cfg(feature = "mlock")]
+            _lock: lock,
+        }
+    }
+    #[must_use]
+    /// Borrow the contents as a [`str`].
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+    /// Make sure there is room for `additional` more bytes.
+    ///
+    /// Rather than letting the [`String`] reallocate and leave a copy of the old contents behind,
+    /// move the contents into a new, larger buffer and zero the old one.
+    fn reserve(&mut self, additional: usize) {
+        let required = self.inner.len() + additional;
+        if required <= self.inner.capacity() {
+            return;
+        }
+        let mut grown = Self::with_capacity(required.max(self.inner.capacity() * 2));
+        grown.inner.push_str(&self.inner);
+        *self = grown;
+    }
+    /// Append a [`char`].
+    pub fn push(&mut self, c: char) {
+        self.reserve(c.len_utf8());
+        self.inner.push(c);
+    }
+    /// Append a [`str`].
+    pub fn push_str(&mut self, s: &str) {
+        self.reserve(s.len());
+        self.inner.push_str(s);
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.inner.zeroize();
+    }
+}
+
+impl Clone for SecretString {
+    fn clone(&self) -> Self {
+        Self::from(self.as_str())
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.inner
+    }
+}
+
+/// Mutating through a [`str`] cannot change the length, so the buffer is never reallocated.
+impl DerefMut for SecretString {
+    fn deref_mut(&mut self) -> &mut str {
+        &mut self.inner
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        let mut result = Self::with_capacity(value.len());
+        result.push_str(value);
+        result
+    }
+}
+
+impl FromIterator<char> for SecretString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut result = Self::with_capacity(iter.size_hint().0);
+        for c in iter {
+            result.push(c);
+        }
+        result
+    }
+}
+
+impl PartialEq for SecretString {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for SecretString {}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.inner == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.inner == *other
+    }
+}
+
+impl PartialEq<String> for SecretString {
+    fn eq(&self, other: &String) -> bool {
+        self.inner == *other
+    }
+}
+
+impl PartialEq<SecretString> for str {
+    fn eq(&self, other: &SecretString) -> bool {
+        *self == other.inner
+    }
+}
+
+impl PartialEq<SecretString> for String {
+    fn eq(&self, other: &SecretString) -> bool {
+        *self == other.inner
+    }
+}
+
+/// Keep secrets out of logs and panic messages.
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretString(***)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_str_grows() {
+        let mut secret = SecretString::with_capacity(2);
+        secret.push_str("correct");
+        secret.push(' ');
+        secret.push_str("horse");
+        assert_eq!(secret, "correct horse");
+        assert!(secret.inner.capacity() >= secret.len());
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let secret: SecretString = ['4', '2'].into_iter().collect();
+        assert_eq!(secret, "42");
+    }
+
+    #[test]
+    fn test_deref_mut_in_place() {
+        let mut secret = SecretString::from("staple");
+        let ptr = secret.as_ptr();
+        secret.make_ascii_uppercase();
+        assert_eq!(secret, "STAPLE");
+        assert_eq!(ptr, secret.as_ptr());
+    }
+
+    #[test]
+    fn test_debug_redacted() {
+        let secret = SecretString::from("battery");
+        assert!(!format!("{secret:?}").contains("battery"));
+    }
+}
@@ -5,38 +5,31 @@
 )]
 #![cfg(test)]
 
-//provides:
-//static WORDLIST: &[&str] = &[...]
-
-include!(concat!(env!("OUT_DIR"), "/wordlist.rs"));
-
 use rand::SeedableRng;
-use rand::TryRngCore;
 use rand::rngs::SmallRng;
 
 use crate::config::ConfigBuilder;
 use crate::password_maker::PasswordMaker;
+use crate::secret::SecretString;
 
 /// Makes a [`PasswordMaker`] with reproducible random output and a small wordlist.
 #[must_use]
 pub fn make_seeded_maker(seed: u64) -> PasswordMaker<SmallRng> {
-    let rng = SmallRng::seed_from_u64(seed).unwrap_err();
-    PasswordMaker {
-        rng,
-        config: ConfigBuilder::new().build().unwrap(),
-        wordlist: make_wordlist(),
-    }
+    let mut maker = make_seeded_maker_big_list(seed);
+    maker.wordlist = make_wordlist()
+        .into_iter()
+        .map(String::into_boxed_str)
+        .collect();
+    maker
 }
 
 /// Makes a [`PasswordMaker`] with reproducible random output and a real wordlist.
 #[must_use]
 pub fn make_seeded_maker_big_list(seed: u64) -> PasswordMaker<SmallRng> {
-    let rng = SmallRng::seed_from_u64(seed).unwrap_err();
-    PasswordMaker {
-        rng,
-        config: ConfigBuilder::new().build().unwrap(),
-        wordlist: WORDLIST.iter().map(|s| String::from(*s)).collect(),
-    }
+    PasswordMaker::with_rng(
+        SmallRng::seed_from_u64(seed),
+        ConfigBuilder::new().build().unwrap(),
+    )
 }
 
 /// Makes a small, easy to work with wordlist.
@@ -49,6 +42,21 @@ pub fn make_wordlist() -> Vec<String> {
     .collect()
 }
 
+/// Makes [`make_wordlist`] out of [`SecretString`]s, as the word transformers expect.
+#[must_use]
+pub fn make_secret_wordlist() -> Vec<SecretString> {
+    make_wordlist()
+        .iter()
+        .map(|s| SecretString::from(s.as_str()))
+        .collect()
+}
+
+/// Turn some [`str`]s into [`SecretString`]s.
+#[must_use]
+pub fn secrets(words: &[&str]) -> Vec<SecretString> {
+    words.iter().copied().map(SecretString::from).collect()
+}
+
 /// Check if a &str is uppercase.
 pub fn str_is_uppercase(word: &str) -> bool {
     word.chars().all(char::is_uppercase)
@@ -4,6 +4,8 @@ use std::fmt;
 
 use strenum_derive::StrEnum;
 
+use crate::consts::DIGIT_ALPHABET;
+
 /// Change this type to support a wider range of integer values (lower or higher)
 type MinimalSupportedInteger = u8;
 
@@ -55,6 +57,8 @@ where
     const NAME: &'static str;
     /// Constant references to enum member name, enum member pairs, implemented by derive macro.
     const NAME_MEMBER_ARR: &[(&str, Self)];
+    /// Constant references to the enum member names, implemented by derive macro.
+    const NAMES: &[&str];
     /// Convert an enum member into its kebab-case name, implemented by derive macro.
     fn to_static_str(&self) -> &'static str;
     /// Iterate over enum member name, enum member pairs, implemented by derive macro.
@@ -72,12 +76,8 @@ where
             .map(|(_, e)| e)
             .ok_or_else(|| {
                 // lazily generate error message
-                // TODO add const [] of just &'static str member names
                 // TODO consider making valid_choices const
-                let valid_choices = Self::into_iter()
-                    .map(|(s, _)| *s)
-                    .collect::<Vec<&str>>()
-                    .join(", ");
+                let valid_choices = Self::NAMES.join(", ");
                 let parent = Self::NAME;
                 let msg = format!(
                     "`{member}` is not a valid {parent}. Possible choices: {valid_choices}"
@@ -112,6 +112,8 @@ pub enum WordTransformationType {
     CapitalizeLast,
     /// cORRECT hORSE bATTERY sTAPLE
     CapitalizeNotFirst,
+    /// Correct horse battery staple
+    CapitalizeFirstWord,
     /// correct HORSE battery STAPLE
     #[default]
     AlternatingLowerUpper,
@@ -121,6 +123,109 @@ pub enum WordTransformationType {
     RandomUpperLower,
 }
 
+/// The parts of speech a template can ask for, see [`crate::password_maker::part_of_speech_wordlist`].
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartOfSpeech {
+    /// an adjective, such as brilliant
+    Adj,
+    /// a noun, such as otter
+    #[default]
+    Noun,
+    /// a verb in the past tense, such as twisted
+    Verb,
+    /// an adverb, such as swiftly
+    Adv,
+}
+
+/// A wordlist a word slot can draw from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WordSource {
+    /// the built-in wordlist with this name
+    Builtin(&'static str),
+    /// the built-in words which are this part of speech
+    Part(PartOfSpeech),
+    /// the words read from a custom wordlist
+    Custom,
+}
+
+impl fmt::Display for WordSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin(name) => write!(f, "{name}"),
+            Self::Part(part) => write!(f, "{part}"),
+            Self::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+/// A wordlist a word slot draws from, and how likely each of its words is relative to those of the slot's other wordlists.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SlotSource {
+    /// where the words come from
+    pub source: WordSource,
+    /// how likely each word of `source` is
+    pub weight: u8,
+}
+
+/// `name`, or `name:weight` unless the weight is 1.
+impl fmt::Display for SlotSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weight == 1 {
+            write!(f, "{}", self.source)
+        } else {
+            write!(f, "{}:{}", self.source, self.weight)
+        }
+    }
+}
+
+/// The character classes digits can be drawn from, when no digit-characters are given.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DigitClass {
+    /// 0-9
+    #[default]
+    Digits,
+    /// 0-9 and a-f
+    Hex,
+    /// A-Z
+    Upper,
+    /// a-z
+    Lower,
+    /// 0-9, A-Z and a-z
+    Alphanumeric,
+}
+
+impl DigitClass {
+    /// The characters in the class, in ascending order.
+    #[must_use]
+    pub fn characters(self) -> Vec<char> {
+        match self {
+            Self::Digits => DIGIT_ALPHABET.to_vec(),
+            Self::Hex => DIGIT_ALPHABET.into_iter().chain('a'..='f').collect(),
+            Self::Upper => ('A'..='Z').collect(),
+            Self::Lower => ('a'..='z').collect(),
+            Self::Alphanumeric => DIGIT_ALPHABET
+                .into_iter()
+                .chain('A'..='Z')
+                .chain('a'..='z')
+                .collect(),
+        }
+    }
+}
+
+/// The different places digits can be put.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DigitPlacement {
+    /// put digits-before before the first word and digits-after after the last
+    #[default]
+    Ends,
+    /// put each group of digits in a random gap between, before or after the words
+    RandomGap,
+    /// attach digits-before to the front of a random word, and digits-after to the back of one
+    Attached,
+    /// put digits-before before the first word, and a group of digits-after after every word
+    EveryGap,
+}
+
 /// The different ways padding can be applied.
 #[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PaddingType {
@@ -143,6 +248,80 @@ pub enum PaddingType {
     Adaptive,
 }
 
+impl PaddingType {
+    /// Whether padding may be added to the front and to the back of a password.
+    #[must_use]
+    pub const fn ends(self) -> [bool; 2] {
+        match self {
+            Self::None => [false, false],
+            Self::FixedFront | Self::AdaptiveFront => [true, false],
+            Self::FixedBack | Self::AdaptiveBack | Self::Adaptive => [false, true],
+            Self::FixedBoth | Self::Fixed => [true, true],
+        }
+    }
+}
+
+/// The different ways padding characters can be chosen.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PaddingFill {
+    /// choose one of padding-characters and repeat it at both ends
+    #[default]
+    Same,
+    /// choose one of padding-front and one of padding-back, and repeat each at its end
+    Independent,
+    /// choose every padding character independently from padding-front and padding-back
+    Random,
+}
+
+/// The different ways separators can be chosen.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SeparatorType {
+    /// choose one separator-characters and use it in every gap
+    #[default]
+    Single,
+    /// choose one of separator-characters for each gap independently
+    PerGap,
+    /// use separator-sequence in order, starting over when it runs out
+    Sequence,
+}
+
+/// What to do about words that run together, with no separator or change of case between them,
+/// when two of them joined can read as two others, like `in` `sideways` and `inside` `ways`.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JoinedWords {
+    /// keep every word, but warn and count the lost entropy
+    #[default]
+    Warn,
+    /// leave out words which start another word, so that passwords can only be read one way
+    PrefixFree,
+}
+
+/// The keyboards whose typing costs can be estimated.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// US QWERTY
+    #[default]
+    Qwerty,
+    /// French AZERTY
+    Azerty,
+    /// German QWERTZ
+    Qwertz,
+    /// US Dvorak
+    Dvorak,
+    /// a phone's on-screen keyboard, with digits and symbols on separate layers
+    Mobile,
+}
+
+/// Sets of defaults for where passwords will be typed, see [`crate::consts::mobile`].
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// the usual defaults, for a computer keyboard
+    #[default]
+    Desktop,
+    /// a single capital, and separators and padding from the first symbol layer of a phone keyboard
+    Mobile,
+}
+
 /// The different random number generator options.
 #[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum RngType {
@@ -153,6 +332,48 @@ pub enum RngType {
     Csprng,
 }
 
+/// The CLI subcommands.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// make passwords
+    #[default]
+    Generate,
+    /// estimate the strength of passwords
+    Entropy,
+    /// make passwords and show how costly they are to type
+    Typing,
+    /// show wordlist statistics, or check a custom wordlist
+    Wordlist,
+    /// print, check or export the effective config
+    Config,
+    /// print a shell completion script
+    Completions,
+}
+
+/// What the `config` subcommand does with the effective config.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// describe every setting
+    #[default]
+    Print,
+    /// only validate
+    Check,
+    /// print the command line options which reproduce the config
+    Export,
+}
+
+/// The shells for which completions can be generated.
+#[derive(StrEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Shell {
+    /// the Bourne Again shell
+    #[default]
+    Bash,
+    /// the Z shell
+    Zsh,
+    /// the friendly interactive shell
+    Fish,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -177,6 +398,11 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_strenum_names() {
+        assert_eq!(RngType::NAMES, ["os-rng", "csprng"]);
+    }
+
     #[test]
     fn test_strenum_to_static_str() {
         assert_eq!("os-rng", RngType::OsRng.to_static_str());
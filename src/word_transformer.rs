@@ -1,125 +1,162 @@
 //! The different ways that words can be transformed.
+//!
+//! Words are transformed in place so that no stray copies of them are left behind.
 use rand::prelude::*;
 
+use crate::secret::SecretString;
+use crate::types::WordTransformationType;
+
 /// correct horse battery staple
 #[must_use]
-pub fn lower(words: Vec<String>) -> Vec<String> {
+pub fn lower(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for word in &mut words {
+        word.make_ascii_lowercase();
+    }
     words
-        .into_iter()
-        .map(|word| word.to_ascii_lowercase())
-        .collect()
 }
 
 /// CORRECT HORSE BATTERY STAPLE
 #[must_use]
-pub fn upper(words: Vec<String>) -> Vec<String> {
+pub fn upper(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for word in &mut words {
+        word.make_ascii_uppercase();
+    }
     words
-        .into_iter()
-        .map(|word| word.to_ascii_uppercase())
-        .collect()
 }
 
 /// Correct Horse Battery Staple
 #[must_use]
-pub fn capitalize_first(words: Vec<String>) -> Vec<String> {
+pub fn capitalize_first(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for word in &mut words {
+        capitalize_first_char(word);
+    }
     words
-        .into_iter()
-        .map(|w| capitalize_first_char(&w))
-        .collect()
 }
 
 /// correcT horsE batterY staplE
 #[must_use]
-pub fn capitalize_last(words: Vec<String>) -> Vec<String> {
+pub fn capitalize_last(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for word in &mut words {
+        capitalize_last_char(word);
+    }
     words
-        .into_iter()
-        .map(|w| capitalize_last_char(&w))
-        .collect()
 }
 
 /// cORRECT hORSE bATTERY sTAPLE
 #[must_use]
-pub fn capitalize_not_first(words: Vec<String>) -> Vec<String> {
+pub fn capitalize_not_first(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for word in &mut words {
+        capitalize_not_first_char(word);
+    }
+    words
+}
+
+/// Correct horse battery staple
+#[must_use]
+pub fn capitalize_first_word(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for (i, word) in words.iter_mut().enumerate() {
+        word.make_ascii_lowercase();
+        if i == 0 {
+            capitalize_first_char(word);
+        }
+    }
     words
-        .into_iter()
-        .map(|w| capitalize_not_first_char(&w))
-        .collect()
 }
 
 /// correct HORSE battery STAPLE
 #[must_use]
-pub fn alternating_lower_upper(words: Vec<String>) -> Vec<String> {
+pub fn alternating_lower_upper(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for (i, word) in words.iter_mut().enumerate() {
+        if i % 2 == 0 {
+            word.make_ascii_lowercase();
+        } else {
+            word.make_ascii_uppercase();
+        }
+    }
     words
-        .into_iter()
-        .enumerate()
-        .map(|(i, word)| {
-            if i % 2 == 0 {
-                word.to_ascii_lowercase()
-            } else {
-                word.to_ascii_uppercase()
-            }
-        })
-        .collect()
 }
 
 /// CORRECT horse BATTERY staple
 #[must_use]
-pub fn alternating_upper_lower(words: Vec<String>) -> Vec<String> {
+pub fn alternating_upper_lower(mut words: Vec<SecretString>) -> Vec<SecretString> {
+    for (i, word) in words.iter_mut().enumerate() {
+        if i % 2 == 0 {
+            word.make_ascii_uppercase();
+        } else {
+            word.make_ascii_lowercase();
+        }
+    }
     words
-        .into_iter()
-        .enumerate()
-        .map(|(i, word)| {
-            if i % 2 == 0 {
-                word.to_ascii_uppercase()
-            } else {
-                word.to_ascii_lowercase()
-            }
-        })
-        .collect()
 }
 
 /// correct HORSE battery staple
-pub fn random_upper_lower(rng: &mut (impl Rng + ?Sized), words: Vec<String>) -> Vec<String> {
+pub fn random_upper_lower(
+    rng: &mut (impl Rng + ?Sized),
+    mut words: Vec<SecretString>,
+) -> Vec<SecretString> {
+    for word in &mut words {
+        if rng.random::<bool>() {
+            word.make_ascii_uppercase();
+        } else {
+            word.make_ascii_lowercase();
+        }
+    }
     words
-        .into_iter()
-        .map(|word| {
-            if rng.random::<bool>() {
-                word.to_ascii_uppercase()
-            } else {
-                word.to_ascii_lowercase()
-            }
-        })
-        .collect()
+}
+
+/// Every form `word` may take under `transformation`.
+///
+/// Unlike the transformations themselves, this works on copies, so it is only meant for wordlist entries.
+#[must_use]
+pub fn forms(word: &str, transformation: WordTransformationType) -> Vec<String> {
+    let apply = |f: fn(&mut str)| {
+        let mut result = word.to_owned();
+        f(&mut result);
+        result
+    };
+    match transformation {
+        WordTransformationType::None => vec![word.to_owned()],
+        WordTransformationType::Lower => vec![word.to_ascii_lowercase()],
+        WordTransformationType::Upper => vec![word.to_ascii_uppercase()],
+        WordTransformationType::CapitalizeFirst => vec![apply(capitalize_first_char)],
+        WordTransformationType::CapitalizeLast => vec![apply(capitalize_last_char)],
+        WordTransformationType::CapitalizeNotFirst => vec![apply(capitalize_not_first_char)],
+        WordTransformationType::CapitalizeFirstWord => {
+            let lower = word.to_ascii_lowercase();
+            let mut first = lower.clone();
+            capitalize_first_char(&mut first);
+            vec![first, lower]
+        }
+        WordTransformationType::AlternatingLowerUpper
+        | WordTransformationType::AlternatingUpperLower
+        | WordTransformationType::RandomUpperLower => {
+            vec![word.to_ascii_lowercase(), word.to_ascii_uppercase()]
+        }
+    }
+}
+
+/// The byte length of the first character of `word`, or 0 if it is empty.
+fn first_char_len(word: &str) -> usize {
+    word.chars().next().map_or(0, char::len_utf8)
 }
 
 /// foo -> Foo
-fn capitalize_first_char(word: &str) -> String {
-    let first = word.chars().take(1).map(|c| c.to_ascii_uppercase());
-    first.chain(word.chars().skip(1)).collect()
+fn capitalize_first_char(word: &mut str) {
+    let end = first_char_len(word);
+    word[..end].make_ascii_uppercase();
 }
 
 /// foo -> foO
-fn capitalize_last_char(word: &str) -> String {
+fn capitalize_last_char(word: &mut str) {
     // UTF character length weirdness reminder
-    let num_chars = word.chars().count();
-    let len_minus_1 = num_chars.saturating_sub(1);
-    word.chars()
-        .take(len_minus_1)
-        .chain(
-            word.chars()
-                .skip(len_minus_1)
-                .take(1)
-                .map(|c| c.to_ascii_uppercase()),
-        )
-        .collect()
+    let start = word.char_indices().last().map_or(0, |(i, _)| i);
+    word[start..].make_ascii_uppercase();
 }
 
 /// foo -> fOO
-fn capitalize_not_first_char(word: &str) -> String {
-    word.chars()
-        .take(1)
-        .chain(word.chars().skip(1).map(|c| c.to_ascii_uppercase()))
-        .collect()
+fn capitalize_not_first_char(word: &mut str) {
+    let start = first_char_len(word);
+    word[start..].make_ascii_uppercase();
 }
 
 // TODO these tests would make great doctests
@@ -129,44 +166,74 @@ mod tests {
     use crate::test_helpers::*;
     use rand::rngs::SmallRng;
 
+    /// Apply an in place transformation to a copy of `word`.
+    fn apply(f: fn(&mut str), word: &str) -> String {
+        let mut result = word.to_owned();
+        f(&mut result);
+        result
+    }
+
     #[test]
     fn test_capitalize_first() {
-        assert_eq!("Foo".to_owned(), capitalize_first_char("foo"));
+        assert_eq!("Foo".to_owned(), apply(capitalize_first_char, "foo"));
     }
 
     #[test]
     fn test_capitalize_first_empty() {
-        assert_eq!(String::new(), capitalize_first_char(""));
+        assert_eq!(String::new(), apply(capitalize_first_char, ""));
     }
 
     #[test]
     fn test_capitalize_last() {
-        assert_eq!("foO".to_owned(), capitalize_last_char("foo"));
+        assert_eq!("foO".to_owned(), apply(capitalize_last_char, "foo"));
     }
 
     #[test]
     fn test_capitalize_last_empty() {
-        assert_eq!(String::new(), capitalize_last_char(""));
+        assert_eq!(String::new(), apply(capitalize_last_char, ""));
+    }
+
+    #[test]
+    fn test_capitalize_last_multibyte() {
+        assert_eq!("fooé".to_owned(), apply(capitalize_last_char, "fooé"));
+        assert_eq!("éoO".to_owned(), apply(capitalize_last_char, "éoo"));
     }
 
     #[test]
     fn test_capitalize_not_first() {
-        assert_eq!("fOO".to_owned(), capitalize_not_first_char("foo"));
+        assert_eq!("fOO".to_owned(), apply(capitalize_not_first_char, "foo"));
     }
 
     #[test]
     fn test_capitalize_not_first_empty() {
-        assert_eq!(String::new(), capitalize_not_first_char(""));
+        assert_eq!(String::new(), apply(capitalize_not_first_char, ""));
     }
 
     #[test]
     fn test_capitalize_not_first_len_1() {
-        assert_eq!("a".to_owned(), capitalize_not_first_char("a"));
+        assert_eq!("a".to_owned(), apply(capitalize_not_first_char, "a"));
+    }
+
+    #[test]
+    fn test_forms() {
+        assert_eq!(forms("Foo", WordTransformationType::None), ["Foo"]);
+        assert_eq!(
+            forms("foo", WordTransformationType::CapitalizeLast),
+            ["foO"]
+        );
+        assert_eq!(
+            forms("fOO", WordTransformationType::CapitalizeFirstWord),
+            ["Foo", "foo"]
+        );
+        assert_eq!(
+            forms("Foo", WordTransformationType::RandomUpperLower),
+            ["foo", "FOO"]
+        );
     }
 
     #[test]
     fn test_word_transformer_lower() {
-        let result = lower(make_wordlist());
+        let result = lower(make_secret_wordlist());
         for word in result {
             assert!(str_is_lowercase(&word));
         }
@@ -174,7 +241,7 @@ mod tests {
 
     #[test]
     fn test_word_transformer_upper() {
-        let result = upper(make_wordlist());
+        let result = upper(make_secret_wordlist());
         for word in result {
             assert!(str_is_uppercase(&word));
         }
@@ -182,30 +249,31 @@ mod tests {
 
     #[test]
     fn test_word_transformer_capitalize_first() {
-        let result = capitalize_first(vec!["foo".to_owned(), "bar".to_owned()]);
+        let result = capitalize_first(secrets(&["foo", "bar"]));
         assert!(result[0] == "Foo" && result[1] == "Bar");
     }
 
     #[test]
     fn test_word_transformer_capitalize_last() {
-        let result = capitalize_last(vec!["foo".to_owned(), "bar".to_owned()]);
+        let result = capitalize_last(secrets(&["foo", "bar"]));
         assert!(result[0] == "foO" && result[1] == "baR");
     }
 
     #[test]
     fn test_word_transformer_capitalize_not_first() {
-        let result = capitalize_not_first(vec!["foo".to_owned(), "bar".to_owned()]);
+        let result = capitalize_not_first(secrets(&["foo", "bar"]));
         assert!(result[0] == "fOO" && result[1] == "bAR");
     }
 
+    #[test]
+    fn test_word_transformer_capitalize_first_word() {
+        let result = capitalize_first_word(secrets(&["foo", "BAR", "baz"]));
+        assert!(result[0] == "Foo" && result[1] == "bar" && result[2] == "baz");
+    }
+
     #[test]
     fn test_word_transformer_alternating_lower_upper() {
-        let sample = vec![
-            "foo".to_owned(),
-            "bar".to_owned(),
-            "baz".to_owned(),
-            "bee".to_owned(),
-        ];
+        let sample = secrets(&["foo", "bar", "baz", "bee"]);
         let result = alternating_lower_upper(sample);
         println!("{result:?}");
         assert_eq!(result[0], "foo");
@@ -216,12 +284,7 @@ mod tests {
 
     #[test]
     fn test_word_transformer_alternating_upper_lower() {
-        let sample = vec![
-            "foo".to_owned(),
-            "bar".to_owned(),
-            "baz".to_owned(),
-            "bee".to_owned(),
-        ];
+        let sample = secrets(&["foo", "bar", "baz", "bee"]);
         let result = alternating_upper_lower(sample);
         println!("{result:?}");
         assert_eq!(result[0], "FOO");
@@ -233,11 +296,11 @@ mod tests {
     #[test]
     fn test_word_transformer_random() {
         let mut rng = SmallRng::seed_from_u64(1);
-        let result_1 = random_upper_lower(&mut rng, vec!["hello".to_owned(), "world".to_owned()]);
-        assert_eq!("HELLO", &result_1[0]);
-        assert_eq!("WORLD", &result_1[1]);
-        let result_2 = random_upper_lower(&mut rng, vec!["hello".to_owned(), "world".to_owned()]);
-        assert_eq!("hello", &result_2[0]);
-        assert_eq!("WORLD", &result_2[1]);
+        let result_1 = random_upper_lower(&mut rng, secrets(&["hello", "world"]));
+        assert_eq!("HELLO", result_1[0].as_str());
+        assert_eq!("WORLD", result_1[1].as_str());
+        let result_2 = random_upper_lower(&mut rng, secrets(&["hello", "world"]));
+        assert_eq!("hello", result_2[0].as_str());
+        assert_eq!("WORLD", result_2[1].as_str());
     }
 }
@@ -0,0 +1,792 @@
+//! Wordlist statistics and validation.
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::iter;
+use std::sync::Arc;
+use std::sync::LazyLock;
+
+use crate::config::Config;
+use crate::consts::AMBIGUOUS_CHARACTERS;
+use crate::keyboard;
+use crate::word_transformer;
+
+/// The built-in blocklist, sorted, see [`default_blocklist`].
+static DEFAULT_BLOCKLIST: LazyLock<Vec<Box<str>>> = LazyLock::new(|| {
+    let mut words = parse(include_str!("../wordlists/blocklist.txt"));
+    words.sort_unstable();
+    words
+});
+
+/// Words which some would rather not see in an issued password, left out with `--blocklist`.
+#[must_use]
+pub fn default_blocklist() -> &'static [Box<str>] {
+    &DEFAULT_BLOCKLIST
+}
+
+/// Whether `word` is one of the words `config` blocks, ignoring case.
+#[must_use]
+pub fn is_blocked(word: &str, config: &Config) -> bool {
+    if !config.blocks_words() {
+        return false;
+    }
+    let word = word.to_lowercase();
+    let builtin: &[Box<str>] = if config.blocklist {
+        default_blocklist()
+    } else {
+        &[]
+    };
+    [builtin, &config.excluded_words].iter().any(|blocked| {
+        blocked
+            .binary_search_by(|b| (**b).cmp(word.as_str()))
+            .is_ok()
+    })
+}
+
+/// Whether a word `config` blocks shows up where `first` runs into `second`,
+/// such as "shit" in "pushit" made from "push" and "it".
+///
+/// Only blocked words which straddle the two are found, those within either word are up to [`is_blocked`].
+#[must_use]
+pub fn joins_blocked(first: &str, second: &str, config: &Config) -> bool {
+    config.blocked_words().any(|blocked| {
+        (1..blocked.len())
+            .filter(|&i| blocked.is_char_boundary(i))
+            .any(|i| {
+                let (head, tail) = blocked.split_at(i);
+                ends_with_ignore_case(first, head) && starts_with_ignore_case(second, tail)
+            })
+    })
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+fn ends_with_ignore_case(text: &str, suffix: &str) -> bool {
+    text.len()
+        .checked_sub(suffix.len())
+        .and_then(|i| text.get(i..))
+        .is_some_and(|end| end.eq_ignore_ascii_case(suffix))
+}
+
+/// Whether `c` is easily mistaken for another character.
+#[must_use]
+pub fn is_ambiguous(c: char) -> bool {
+    AMBIGUOUS_CHARACTERS.contains(&c)
+}
+
+/// Whether `word` may be chosen under `config`.
+///
+/// When avoiding ambiguous characters, a word is only allowed if no form the
+/// configured transformation can give it contains any.
+/// When typing easily, a word is only allowed if its letters are all on the keyboard
+/// without shift or Alt Gr, whatever case the transformation gives it.
+/// Blocked words are never allowed.
+#[must_use]
+pub fn is_word_allowed(word: &str, config: &Config) -> bool {
+    (config.word_min_length as usize..=config.word_max_length as usize)
+        .contains(&word.chars().count())
+        && !is_blocked(word, config)
+        && !(config.avoid_ambiguous
+            && word_transformer::forms(word, config.word_transformation)
+                .iter()
+                .any(|form| form.chars().any(is_ambiguous)))
+        && (!config.easy_typing || keyboard::is_easy_word(word, config.keyboard))
+}
+
+/// Count the words in `words` which may be chosen under `config`.
+#[must_use]
+pub fn count_allowed(words: &[Box<str>], config: &Config) -> usize {
+    allowed_words(words, config).len()
+}
+
+/// Count the words in `words` which may be chosen under `config`, by length in characters.
+#[must_use]
+pub fn allowed_length_counts(words: &[Box<str>], config: &Config) -> BTreeMap<usize, usize> {
+    let mut length_counts = BTreeMap::new();
+    for word in allowed_words(words, config) {
+        *length_counts.entry(word.chars().count()).or_insert(0) += 1;
+    }
+    length_counts
+}
+
+/// A wordlist, and how likely a word slot is to draw each of its words relative to those of the slot's other wordlists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedWordlist {
+    /// the words
+    pub words: Arc<[Box<str>]>,
+    /// how likely each of `words` is
+    pub weight: u8,
+}
+
+/// Count the words a word slot which mixes `wordlists` may choose under `config`, by length in characters.
+///
+/// The slot draws each word with a chance in proportion to the weight of its wordlist,
+/// or the sum of their weights if it is in more than one, so some words can be more likely than others.
+/// The most likely words count as one word each and the others as their chance relative to them,
+/// so the counts add up to one over the chance of the most likely word, and estimates based on them err low.
+/// For wordlists of equal weight, they are the [`allowed_length_counts`] of all of their words.
+#[must_use]
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "wordlists are nowhere near 2^52 words"
+)]
+pub fn mixed_length_counts(
+    wordlists: &[WeightedWordlist],
+    config: &Config,
+) -> BTreeMap<usize, f64> {
+    let allowed = wordlists
+        .iter()
+        .map(|wordlist| {
+            (
+                allowed_words(&wordlist.words, config),
+                f64::from(wordlist.weight),
+            )
+        })
+        .collect::<Vec<_>>();
+    let total_weight = allowed
+        .iter()
+        .map(|(words, weight)| words.len() as f64 * weight)
+        .sum::<f64>();
+    let mut chances = HashMap::<&str, f64>::new();
+    for (words, weight) in &allowed {
+        for word in words {
+            *chances.entry(word).or_insert(0.0) += weight / total_weight;
+        }
+    }
+    let most_likely = chances.values().copied().fold(0.0, f64::max);
+    let mut length_counts = BTreeMap::new();
+    for (word, chance) in chances {
+        *length_counts.entry(word.chars().count()).or_insert(0.0) += chance / most_likely;
+    }
+    length_counts
+}
+
+/// The words in `words` which may be chosen under `config`.
+///
+/// On top of [`is_word_allowed`], this leaves out words which start another when [`Config::drops_prefixes`].
+#[must_use]
+pub fn allowed_words<'a>(words: &'a [Box<str>], config: &Config) -> Vec<&'a str> {
+    let allowed = words
+        .iter()
+        .filter(|w| is_word_allowed(w, config))
+        .map(AsRef::as_ref)
+        .collect::<Vec<&str>>();
+    if !config.drops_prefixes() {
+        return allowed;
+    }
+    let starts_another = starts_another(&allowed);
+    allowed
+        .into_iter()
+        .zip(starts_another)
+        .filter(|(_, starts_another)| !starts_another)
+        .map(|(word, _)| word)
+        .collect()
+}
+
+/// For each of `words`, whether it is the start of another, ignoring case.
+///
+/// Leaving out every word which does makes the rest prefix free.
+#[must_use]
+pub fn starts_another(words: &[&str]) -> Vec<bool> {
+    let lowercase = words
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<String>>();
+    let mut order = (0..words.len()).collect::<Vec<usize>>();
+    order.sort_unstable_by(|a, b| lowercase[*a].cmp(&lowercase[*b]));
+    let mut result = vec![false; words.len()];
+    // sorted, a word starts another exactly when it starts the next different word
+    for (i, &index) in order.iter().enumerate() {
+        let word = &lowercase[index];
+        result[index] = order[i + 1..]
+            .iter()
+            .map(|&next| &lowercase[next])
+            .find(|next| *next != word)
+            .is_some_and(|next| next.starts_with(word.as_str()));
+    }
+    result
+}
+
+/// Lowercase, sort and deduplicate `words`.
+fn lowercase_set<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut words = words
+        .into_iter()
+        .map(str::to_lowercase)
+        .collect::<Vec<String>>();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+/// The words in `sorted` which start with `prefix`, in order.
+fn starting_with<'a>(sorted: &'a [String], prefix: &str) -> impl Iterator<Item = &'a String> {
+    let start = sorted.partition_point(|word| word.as_str() < prefix);
+    sorted[start..]
+        .iter()
+        .take_while(move |word| word.starts_with(prefix))
+}
+
+/// Whether any run of `words` joined together can only be split back into words one way, ignoring case.
+///
+/// This is the Sardinas-Patterson test: it follows the suffixes left dangling when
+/// two ways of reading the same text part, and fails if one of them is a whole word.
+#[must_use]
+pub fn is_uniquely_decodable(words: &[&str]) -> bool {
+    let count = words.len();
+    let words = lowercase_set(words.iter().copied());
+    if words.len() < count {
+        // a repeated word reads the same either way
+        return false;
+    }
+    let is_word = |text: &str| {
+        words
+            .binary_search_by(|word| word.as_str().cmp(text))
+            .is_ok()
+    };
+    let mut dangling = HashSet::new();
+    for word in &words {
+        for longer in starting_with(&words, word).filter(|longer| *longer != word) {
+            dangling.insert(longer[word.len()..].to_owned());
+        }
+    }
+    let mut seen = HashSet::new();
+    while !dangling.is_empty() {
+        if dangling.iter().any(|suffix| is_word(suffix)) {
+            return false;
+        }
+        let mut next = HashSet::new();
+        for suffix in &dangling {
+            // words which go on where the suffix stops
+            for word in starting_with(&words, suffix).filter(|word| *word != suffix) {
+                next.insert(word[suffix.len()..].to_owned());
+            }
+            // words which stop partway through the suffix
+            for (i, _) in suffix.char_indices().skip(1) {
+                if is_word(&suffix[..i]) {
+                    next.insert(suffix[i..].to_owned());
+                }
+            }
+        }
+        seen.extend(dangling);
+        dangling = next
+            .into_iter()
+            .filter(|suffix| !seen.contains(suffix))
+            .collect();
+    }
+    true
+}
+
+/// Bits of entropy lost for each pair of adjacent words when `words` run together, ignoring case.
+/// Repeated words are counted once.
+///
+/// Two words chosen from `words` are equally likely, but when joined, like `in` `sideways` and `inside` `ways`,
+/// some pairs spell the same text, so an attacker only has to guess the text.
+/// This is the entropy of choosing the pair which is left once the text is known.
+/// Ambiguity which spans more than two words is not counted, so for longer passwords this is an estimate.
+#[must_use]
+pub fn join_loss(words: &[&str]) -> f64 {
+    let words = lowercase_set(words.iter().copied());
+    let is_word = |text: &str| {
+        words
+            .binary_search_by(|word| word.as_str().cmp(text))
+            .is_ok()
+    };
+    // joined text, and each place it splits into two words
+    let mut splits: HashMap<String, HashSet<usize>> = HashMap::new();
+    for first in &words {
+        for longer in starting_with(&words, first).filter(|longer| *longer != first) {
+            // `first` `second` and `longer` `rest` spell the same text
+            let overlap = &longer[first.len()..];
+            for second in starting_with(&words, overlap) {
+                let rest = &second[overlap.len()..];
+                if !rest.is_empty() && is_word(rest) {
+                    let text = splits.entry(format!("{first}{second}")).or_default();
+                    text.insert(first.len());
+                    text.insert(longer.len());
+                }
+            }
+        }
+    }
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "wordlists are nowhere near 2^52 words"
+    )]
+    let pairs = (words.len() as f64).powi(2);
+    splits
+        .values()
+        .map(|splits| {
+            #[expect(
+                clippy::cast_precision_loss,
+                reason = "a text splits in fewer places than it has characters"
+            )]
+            let ways = splits.len() as f64;
+            ways * ways.log2() / pairs
+        })
+        .sum()
+}
+
+/// The number of ways to choose `n` words, in order and with replacement,
+/// whose lengths add up to at most `budget` characters.
+///
+/// `length_counts` maps each word length to the number of words of that length.
+/// The result is a float as it easily outgrows every integer type.
+#[must_use]
+pub fn count_fitting(length_counts: &BTreeMap<usize, usize>, n: usize, budget: usize) -> f64 {
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "wordlists are nowhere near 2^52 words"
+    )]
+    let length_counts = length_counts
+        .iter()
+        .map(|(length, words)| (*length, *words as f64))
+        .collect();
+    count_fitting_slots(iter::repeat_n(&length_counts, n), budget)
+}
+
+/// The number of ways to choose a word for each slot, whose lengths add up to at most `budget` characters.
+///
+/// `slot_length_counts` maps each word length to the number of words of that length, for each slot,
+/// which may be fractional, see [`mixed_length_counts`].
+#[must_use]
+pub fn count_fitting_slots<'a>(
+    slot_length_counts: impl IntoIterator<Item = &'a BTreeMap<usize, f64>>,
+    budget: usize,
+) -> f64 {
+    // ways[l] is the number of ways to choose the words so far with a total length of exactly l
+    let mut ways = vec![0.0; budget + 1];
+    ways[0] = 1.0;
+    for length_counts in slot_length_counts {
+        let mut next = vec![0.0; budget + 1];
+        for (total, count) in ways.iter().enumerate().filter(|(_, c)| **c > 0.0) {
+            for (length, words) in length_counts.range(..=budget - total) {
+                next[total + length] += count * words;
+            }
+        }
+        ways = next;
+    }
+    ways.iter().sum()
+}
+
+/// Read a wordlist with one word per line.
+///
+/// Surrounding whitespace is trimmed and blank lines are skipped.
+#[must_use]
+pub fn parse(text: &str) -> Vec<Box<str>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(Box::from)
+        .collect()
+}
+
+/// Something that makes a wordlist unfit for making passwords.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// There are no words at all.
+    Empty,
+    /// The word appears more than once, ignoring case, which skews the distribution.
+    Duplicate(String),
+    /// The word contains whitespace, which is easily mangled when typed or copied.
+    Whitespace(String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the wordlist is empty"),
+            Self::Duplicate(word) => write!(f, "`{word}` appears more than once"),
+            Self::Whitespace(word) => write!(f, "`{word}` contains whitespace"),
+        }
+    }
+}
+
+/// Find every [`Problem`] with `words`.
+#[must_use]
+pub fn check(words: &[Box<str>]) -> Vec<Problem> {
+    if words.is_empty() {
+        return vec![Problem::Empty];
+    }
+    let mut problems = Vec::new();
+    let mut seen = HashSet::with_capacity(words.len());
+    for word in words {
+        if word.chars().any(char::is_whitespace) {
+            problems.push(Problem::Whitespace(word.to_string()));
+        }
+        if !seen.insert(word.to_lowercase()) {
+            problems.push(Problem::Duplicate(word.to_string()));
+        }
+    }
+    problems
+}
+
+/// A summary of a wordlist, and of the words `config` allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    /// number of words in the list
+    pub total: usize,
+    /// number of words which may be chosen
+    pub allowed: usize,
+    /// number of words with characters outside of ASCII, which some keyboards can not type
+    pub non_ascii: usize,
+    /// whether no word is the start of another, ignoring case, so that words run together can be split apart again
+    pub prefix_free: bool,
+    /// whether words run together can only be split back into words one way, ignoring case, see [`is_uniquely_decodable`]
+    pub uniquely_decodable: bool,
+    /// the fewest leading characters which tell every word apart, ignoring case, or `None` with duplicates
+    pub unique_prefix_length: Option<usize>,
+    /// the configured minimum word length
+    pub word_min_length: u8,
+    /// the configured maximum word length
+    pub word_max_length: u8,
+    /// number of words of each length, in characters
+    pub length_counts: BTreeMap<usize, usize>,
+}
+
+impl Stats {
+    /// Summarize `words`.
+    #[must_use]
+    pub fn new(words: &[Box<str>], config: &Config) -> Self {
+        let mut length_counts = BTreeMap::new();
+        for word in words {
+            *length_counts.entry(word.chars().count()).or_insert(0) += 1;
+        }
+        let (prefix_free, unique_prefix_length) = prefixes(words);
+        Self {
+            total: words.len(),
+            allowed: count_allowed(words, config),
+            non_ascii: words.iter().filter(|word| !word.is_ascii()).count(),
+            prefix_free,
+            uniquely_decodable: prefix_free
+                || is_uniquely_decodable(&words.iter().map(AsRef::as_ref).collect::<Vec<&str>>()),
+            unique_prefix_length,
+            word_min_length: config.word_min_length,
+            word_max_length: config.word_max_length,
+            length_counts,
+        }
+    }
+}
+
+/// Whether `words` are prefix free, and how many leading characters tell them apart, see [`Stats`].
+fn prefixes(words: &[Box<str>]) -> (bool, Option<usize>) {
+    let mut words = words
+        .iter()
+        .map(|word| word.to_lowercase().chars().collect::<Vec<char>>())
+        .collect::<Vec<Vec<char>>>();
+    words.sort_unstable();
+    // sorted, a word is the start of another exactly when it is the start of the word after it
+    let mut prefix_free = true;
+    let mut unique_prefix_length = Some(0);
+    for pair in words.windows(2) {
+        let common = pair[0]
+            .iter()
+            .zip(&pair[1])
+            .take_while(|(a, b)| a == b)
+            .count();
+        if common == pair[0].len() {
+            prefix_free = false;
+            if common == pair[1].len() {
+                unique_prefix_length = None;
+            }
+        }
+        unique_prefix_length = unique_prefix_length.map(|length| length.max(common + 1));
+    }
+    (prefix_free, unique_prefix_length)
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "words: {}", self.total)?;
+        writeln!(f, "words with non-ASCII characters: {}", self.non_ascii)?;
+        writeln!(
+            f,
+            "prefix free: {}",
+            if self.prefix_free { "yes" } else { "no" }
+        )?;
+        writeln!(
+            f,
+            "uniquely decodable: {}",
+            if self.uniquely_decodable { "yes" } else { "no" }
+        )?;
+        match self.unique_prefix_length {
+            Some(length) => writeln!(f, "unique prefix length: {length}")?,
+            None => writeln!(f, "unique prefix length: none, words repeat")?,
+        }
+        writeln!(
+            f,
+            "words of length {} to {}: {}",
+            self.word_min_length, self.word_max_length, self.allowed
+        )?;
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "wordlists are nowhere near 2^52 words"
+        )]
+        let bits = if self.allowed == 0 {
+            0.0
+        } else {
+            (self.allowed as f64).log2()
+        };
+        writeln!(f, "bits per word: {bits:.2}")?;
+        writeln!(f, "\nlength count")?;
+        for (length, count) in &self.length_counts {
+            writeln!(f, "{length:>6} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use std::slice;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    fn words(words: &[&str]) -> Vec<Box<str>> {
+        words.iter().copied().map(Box::from).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("a\n\n  b \r\nc d\n"), words(&["a", "b", "c d"]));
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(check(&[]), [Problem::Empty]);
+        assert!(check(&words(&["a", "b"])).is_empty());
+        assert_eq!(
+            check(&words(&["a", "b c", "A"])),
+            [
+                Problem::Whitespace("b c".to_owned()),
+                Problem::Duplicate("A".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_word_allowed_avoiding_ambiguous() {
+        let make = |transformation: &str| {
+            ConfigBuilder::new()
+                .word_min_length(Some("1".to_owned()))
+                .word_transformation(Some(transformation.to_owned()))
+                .avoid_ambiguous(Some("true".to_owned()))
+                .build()
+                .unwrap()
+        };
+        let allowed = |word, transformation| is_word_allowed(word, &make(transformation));
+        assert!(!allowed("illinois", "upper"));
+        assert!(!allowed("illinois", "lower"));
+        assert!(allowed("gin", "lower"));
+        assert!(!allowed("gin", "upper"));
+        assert!(allowed("gin", "capitalize-first"));
+        assert!(!allowed("ice", "capitalize-first"));
+        assert!(!allowed("snow", "random-upper-lower"));
+        assert!(is_word_allowed("illinois", &Config::default()));
+    }
+
+    #[test]
+    fn test_is_word_allowed_typing_easily() {
+        let make = |keyboard: &str| {
+            ConfigBuilder::new()
+                .word_min_length(Some("1".to_owned()))
+                .keyboard(Some(keyboard.to_owned()))
+                .easy_typing(Some("true".to_owned()))
+                .build()
+                .unwrap()
+        };
+        assert!(is_word_allowed("café", &make("azerty")));
+        assert!(!is_word_allowed("café", &make("qwerty")));
+        assert!(!is_word_allowed("yo-yo", &make("mobile")));
+        assert!(is_word_allowed("yo-yo", &make("qwerty")));
+        assert!(is_word_allowed("café", &Config::default()));
+    }
+
+    #[test]
+    fn test_default_blocklist_is_sorted_lowercase() {
+        let blocklist = default_blocklist();
+        assert!(!blocklist.is_empty());
+        assert!(blocklist.is_sorted());
+        assert!(blocklist.iter().all(|word| **word == *word.to_lowercase()));
+    }
+
+    #[test]
+    fn test_is_blocked() {
+        let config = ConfigBuilder::new()
+            .excluded_words(Some("Horse\nstaple".to_owned()))
+            .build()
+            .unwrap();
+        assert!(is_blocked("horse", &config));
+        assert!(is_blocked("STAPLE", &config));
+        assert!(!is_blocked("poison", &config));
+        assert!(!is_word_allowed("horse", &config));
+        let config = ConfigBuilder::new()
+            .blocklist(Some("true".to_owned()))
+            .build()
+            .unwrap();
+        assert!(is_blocked("poison", &config));
+        assert!(!is_blocked("horse", &config));
+        assert!(!is_blocked("poison", &Config::default()));
+    }
+
+    #[test]
+    fn test_joins_blocked() {
+        let config = ConfigBuilder::new()
+            .blocklist(Some("true".to_owned()))
+            .build()
+            .unwrap();
+        assert!(joins_blocked("push", "it", &config));
+        assert!(joins_blocked("PUSH", "It", &config));
+        assert!(!joins_blocked("it", "push", &config));
+        // within a single word is left to the filter
+        assert!(!joins_blocked("poison", "ivy", &config));
+        assert!(!joins_blocked("push", "it", &Config::default()));
+    }
+
+    #[test]
+    fn test_count_fitting() {
+        let length_counts = BTreeMap::from([(2, 3), (3, 5)]);
+        assert_close(count_fitting(&length_counts, 0, 0), 1.0);
+        assert_close(count_fitting(&length_counts, 2, 3), 0.0);
+        assert_close(count_fitting(&length_counts, 2, 4), 9.0);
+        // 2+2, 2+3 and 3+2
+        assert_close(count_fitting(&length_counts, 2, 5), 39.0);
+        assert_close(count_fitting(&length_counts, 2, 6), 64.0);
+        assert_close(count_fitting(&length_counts, 3, 100), 512.0);
+    }
+
+    #[test]
+    fn test_count_fitting_slots() {
+        let short = BTreeMap::from([(2, 3.0)]);
+        let long = BTreeMap::from([(3, 5.0), (4, 7.0)]);
+        assert_close(count_fitting_slots([&short, &long], 4), 0.0);
+        assert_close(count_fitting_slots([&short, &long], 5), 15.0);
+        assert_close(count_fitting_slots([&long, &short], 6), 36.0);
+        assert_close(count_fitting_slots([&short, &short, &long], 100), 108.0);
+        assert_close(count_fitting_slots([&BTreeMap::from([(2, 0.5)])], 2), 0.5);
+    }
+
+    #[test]
+    fn test_mixed_length_counts() {
+        let config = ConfigBuilder::new()
+            .word_min_length(Some("2".to_owned()))
+            .build()
+            .unwrap();
+        let weighted = |text: &[&str], weight| WeightedWordlist {
+            words: words(text).into(),
+            weight,
+        };
+        let short = weighted(&["aa", "bb"], 1);
+        let long = weighted(&["ccc", "ddd", "eee", "ffff"], 1);
+        assert_eq!(
+            mixed_length_counts(slice::from_ref(&long), &config),
+            BTreeMap::from([(3, 3.0), (4, 1.0)])
+        );
+        assert_eq!(
+            mixed_length_counts(&[short, long.clone()], &config),
+            BTreeMap::from([(2, 2.0), (3, 3.0), (4, 1.0)])
+        );
+        // each short word is twice as likely as each long one
+        let short = weighted(&["aa", "bb"], 2);
+        assert_eq!(
+            mixed_length_counts(&[short.clone(), long], &config),
+            BTreeMap::from([(2, 2.0), (3, 1.5), (4, 0.5)])
+        );
+        // a word in both lists is the most likely
+        let counts = mixed_length_counts(&[short.clone(), weighted(&["aa", "ccc"], 2)], &config);
+        assert_eq!(counts, BTreeMap::from([(2, 1.5), (3, 0.5)]));
+        // words which are not allowed are never drawn, however heavy
+        assert_eq!(
+            mixed_length_counts(&[weighted(&["a"], 9), short], &config),
+            BTreeMap::from([(2, 2.0)])
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let config = ConfigBuilder::new()
+            .word_min_length(Some("2".to_owned()))
+            .word_max_length(Some("3".to_owned()))
+            .build()
+            .unwrap();
+        let stats = Stats::new(&words(&["a", "bb", "cc", "ddd", "éé", "eeee"]), &config);
+        assert_eq!(stats.total, 6);
+        assert_eq!(stats.allowed, 4);
+        assert_eq!(stats.non_ascii, 1);
+        assert!(stats.prefix_free);
+        assert_eq!(stats.unique_prefix_length, Some(1));
+        assert_eq!(
+            stats.length_counts.into_iter().collect::<Vec<_>>(),
+            [(1, 1), (2, 3), (3, 1), (4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(prefixes(&[]), (true, Some(0)));
+        assert_eq!(prefixes(&words(&["a"])), (true, Some(0)));
+        assert_eq!(
+            prefixes(&words(&["abacus", "abdomen", "zoo"])),
+            (true, Some(3))
+        );
+        assert_eq!(prefixes(&words(&["Push", "pushy", "it"])), (false, Some(5)));
+        assert_eq!(prefixes(&words(&["push", "it", "PUSH"])), (false, None));
+    }
+
+    #[test]
+    fn test_starts_another() {
+        assert_eq!(
+            starts_another(&["inside", "In", "ways", "in", "side", "sideways"]),
+            [false, true, false, true, true, false]
+        );
+        assert!(starts_another(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_is_uniquely_decodable() {
+        assert!(is_uniquely_decodable(&[]));
+        assert!(is_uniquely_decodable(&["correct", "horse", "battery"]));
+        // not prefix free, but `a` is always followed by the rest of `ab` or by `b`
+        assert!(is_uniquely_decodable(&["a", "ab", "bb"]));
+        assert!(!is_uniquely_decodable(&[
+            "in", "inside", "sideways", "ways"
+        ]));
+        assert!(!is_uniquely_decodable(&["a", "ab", "b"]));
+        assert!(!is_uniquely_decodable(&["Ab", "ab"]));
+    }
+
+    #[test]
+    fn test_join_loss() {
+        assert_close(join_loss(&["correct", "horse", "battery"]), 0.0);
+        assert_close(join_loss(&["a", "ab", "bb"]), 0.0);
+        // of the 16 pairs, `in` `sideways` and `inside` `ways` spell the same text
+        assert_close(join_loss(&["in", "inside", "sideways", "ways"]), 2.0 / 16.0);
+        // `a` `bc` and `ab` `c` spell the same text
+        assert_close(join_loss(&["a", "ab", "bc", "c"]), 2.0 / 16.0);
+    }
+
+    #[test]
+    fn test_allowed_words_prefix_free() {
+        let words = words(&["inside", "in", "ways", "sideways", "side"]);
+        let make = |joined_words: &str| {
+            ConfigBuilder::new()
+                .word_min_length(Some("2".to_owned()))
+                .separator_characters(Some(String::new()))
+                .word_transformation(Some("lower".to_owned()))
+                .joined_words(Some(joined_words.to_owned()))
+                .build()
+                .unwrap()
+        };
+        assert_eq!(allowed_words(&words, &make("warn")).len(), 5);
+        let allowed = allowed_words(&words, &make("prefix-free"));
+        assert_eq!(allowed, ["inside", "ways", "sideways"]);
+        assert!(is_uniquely_decodable(&allowed));
+        assert_eq!(count_allowed(&words, &make("prefix-free")), 3);
+    }
+}
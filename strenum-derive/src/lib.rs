@@ -1,6 +1,6 @@
 //! Give enums superpowers.
 //!
-//! ```
+//! ```ignore
 //! #[derive(StrEnum, Copy, Clone, Debug)]
 //! pub enum RngType {
 //!     #[default]
@@ -9,7 +9,7 @@
 //! }
 //! ```
 //! expands to:
-//! ```
+//! ```ignore
 //! impl RngType {
 //!     pub const fn default_const() -> Self {
 //!         Self::OsRng
@@ -17,7 +17,8 @@
 //! }
 //! impl StrEnum for RngType {
 //!     const NAME: &'static str = "RngType";
-//!     const NAME_MEMBER_ARR: &[(&'static str, Self)] = &["os-rng", "csprng"];
+//!     const NAME_MEMBER_ARR: &[(&'static str, Self)] = &[("os-rng", Self::OsRng), ("csprng", Self::Csprng)];
+//!     const NAMES: &[&'static str] = &["os-rng", "csprng"];
 //!     fn to_static_str(&self) -> &'static str {
 //!         match self {
 //!             Self::OsRng => "os-rng",
@@ -37,6 +38,7 @@
 //! impl Default for RngType {
 //!     fn default() -> Self { Self::default_const() }
 //! }
+//! ```
 use proc_macro::{self, TokenStream};
 
 use quote::quote;
@@ -71,16 +73,15 @@ pub fn derive(input: TokenStream) -> TokenStream {
         .map(|variant| match variant.fields {
             Fields::Unit => {
                 for attr in &variant.attrs {
-                    if let Meta::Path(path) = &attr.meta {
-                        if let Some(ident) = path.get_ident() {
-                            if ident == "default" {
-                                assert!(
-                                    default_ident_maybe.is_none(),
-                                    "duplicate default helper attributes are not allowed"
-                                );
-                                default_ident_maybe = Some(variant.ident.clone());
-                            }
-                        }
+                    if let Meta::Path(path) = &attr.meta
+                        && let Some(ident) = path.get_ident()
+                        && ident == "default"
+                    {
+                        assert!(
+                            default_ident_maybe.is_none(),
+                            "duplicate default helper attributes are not allowed"
+                        );
+                        default_ident_maybe = Some(variant.ident.clone());
                     }
                 }
                 variant.ident.clone()
@@ -107,6 +108,7 @@ pub fn derive(input: TokenStream) -> TokenStream {
         impl StrEnum for #enum_name {
             const NAME: &'static str = stringify!(#enum_name);
             const NAME_MEMBER_ARR: &[(&'static str, Self)] = &[ #( (#transformed_field_idents, Self::#field_idents) ,)* ];
+            const NAMES: &[&'static str] = &[ #( #transformed_field_idents ,)* ];
             fn to_static_str(&self) -> &'static str {
                 match self {
                     #(Self::#field_idents => #transformed_field_idents,)*
@@ -0,0 +1,802 @@
+//! End to end tests of the `fmn-passgen` binary.
+//!
+//! The CLI defines the project's semantic versioning, so its behaviour is pinned down here:
+//! help output, exit codes, option parsing, and (in debug builds) seeded output.
+use std::env;
+use std::fs;
+use std::iter;
+use std::process;
+use std::process::Output;
+
+use fmn_passgen::password_maker::part_of_speech_wordlist;
+use fmn_passgen::types::Command;
+use fmn_passgen::types::DigitClass;
+use fmn_passgen::types::DigitPlacement;
+use fmn_passgen::types::JoinedWords;
+use fmn_passgen::types::KeyboardLayout;
+use fmn_passgen::types::PaddingFill;
+use fmn_passgen::types::PaddingType;
+use fmn_passgen::types::PartOfSpeech;
+use fmn_passgen::types::Preset;
+use fmn_passgen::types::RngType;
+use fmn_passgen::types::SeparatorType;
+use fmn_passgen::types::StrEnum;
+use fmn_passgen::types::WordTransformationType;
+
+/// Only honoured by debug builds of the binary.
+const TEST_SEED_VAR: &str = "FMN_PASSGEN_TEST_SEED";
+
+fn run(args: &[&str]) -> Output {
+    process::Command::new(env!("CARGO_BIN_EXE_fmn-passgen"))
+        .args(args)
+        .env_remove(TEST_SEED_VAR)
+        .output()
+        .expect("the binary should run")
+}
+
+#[cfg(debug_assertions)]
+fn run_seeded(seed: u64, args: &[&str]) -> Output {
+    process::Command::new(env!("CARGO_BIN_EXE_fmn-passgen"))
+        .args(args)
+        .env(TEST_SEED_VAR, seed.to_string())
+        .output()
+        .expect("the binary should run")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).expect("stdout should be UTF-8")
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).expect("stderr should be UTF-8")
+}
+
+fn member_names<T: StrEnum>() -> impl Iterator<Item = &'static str> {
+    T::NAME_MEMBER_ARR.iter().map(|(name, _)| *name)
+}
+
+fn assert_help(output: &Output) {
+    assert!(output.status.success());
+    let out = stdout(output);
+    assert!(out.starts_with("Usage: "), "{out}");
+    for long in [
+        "--help",
+        "--count",
+        "--wordlist-name",
+        "--custom-wordlist",
+        "--word-count",
+        "--template",
+        "--slot-wordlists",
+        "--word-min-length",
+        "--word-max-length",
+        "--word-transformation",
+        "--digits-before",
+        "--digits-after",
+        "--padding-type",
+        "--padding-length",
+        "--padding-characters",
+        "--separators",
+        "--rng",
+        "--min-upper",
+        "--min-lower",
+        "--min-digits",
+        "--digit-placement",
+        "--digit-class",
+        "--digit-characters",
+        "--padding-fill",
+        "--padding-front",
+        "--padding-back",
+        "--separator-type",
+        "--separator-sequence",
+        "--joined-words",
+        "--preset",
+        "--keyboard",
+        "--easy-typing",
+        "--avoid-ambiguous",
+        "--blocklist",
+        "--exclude-words",
+        "--min-symbols",
+        "--max-length",
+        "--max-repeat",
+        "--explain",
+        "--man",
+    ] {
+        assert!(out.contains(long), "{long} missing from help");
+    }
+    let names = member_names::<WordTransformationType>()
+        .chain(member_names::<DigitClass>())
+        .chain(member_names::<DigitPlacement>())
+        .chain(member_names::<PaddingType>())
+        .chain(member_names::<PaddingFill>())
+        .chain(member_names::<SeparatorType>())
+        .chain(member_names::<JoinedWords>())
+        .chain(member_names::<KeyboardLayout>())
+        .chain(member_names::<Preset>())
+        .chain(member_names::<PartOfSpeech>())
+        .chain(member_names::<RngType>());
+    for name in names {
+        assert!(out.contains(name), "{name} missing from help");
+    }
+    assert!(out.contains("eff-large (7776 words"), "{out}");
+    assert!(stderr(output).is_empty());
+}
+
+#[test]
+fn test_help() {
+    assert_help(&run(&["-h"]));
+    assert_help(&run(&["--help"]));
+}
+
+#[test]
+fn test_free_argument_prints_help() {
+    assert_help(&run(&["unexpected"]));
+    assert_help(&run(&["-w", "3", "unexpected"]));
+    assert_help(&run(&["generate", "unexpected"]));
+    assert_help(&run(&["config", "print", "unexpected"]));
+    assert_help(&run(&["completions"]));
+}
+
+#[test]
+fn test_help_lists_commands() {
+    let out = stdout(&run(&["-h"]));
+    for name in member_names::<Command>() {
+        assert!(out.contains(name), "{name} missing from help");
+    }
+}
+
+#[test]
+fn test_default_output_shape() {
+    let output = run(&[]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).lines().count(), 1);
+    assert!(stderr(&output).is_empty());
+}
+
+#[test]
+fn test_count() {
+    for rng in ["os-rng", "csprng"] {
+        let output = run(&["-c", "5", "-r", rng]);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output).lines().count(), 5);
+    }
+}
+
+#[test]
+fn test_unrecognized_option() {
+    let output = run(&["--not-an-option"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("not-an-option"));
+}
+
+#[test]
+fn test_missing_option_argument() {
+    let output = run(&["-c"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).is_empty());
+    assert!(!stderr(&output).is_empty());
+}
+
+#[test]
+fn test_invalid_numbers() {
+    for (args, message) in [
+        (
+            ["-c", "0"],
+            "`0` must be a positive integer between 1 and 255",
+        ),
+        (
+            ["-w", "abc"],
+            "`abc` must be a positive integer between 0 and 32",
+        ),
+        (
+            ["-w", "33"],
+            "`33` must be a positive integer between 0 and 32",
+        ),
+        (
+            ["-a", "-1"],
+            "`-1` must be a positive integer between 0 and 255",
+        ),
+    ] {
+        let output = run(&args);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stdout(&output).is_empty());
+        assert!(stderr(&output).contains(message), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn test_word_max_length_below_min() {
+    let output = run(&["-m", "8", "-M", "5"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`5` must be a positive integer between 8 and 255"));
+}
+
+#[test]
+fn test_invalid_enums() {
+    for (option, parent) in [
+        ("-W", WordTransformationType::NAME),
+        ("-T", PaddingType::NAME),
+        ("-r", RngType::NAME),
+        ("--wordlist-name", "wordlist"),
+        ("--joined-words", JoinedWords::NAME),
+        ("--keyboard", KeyboardLayout::NAME),
+        ("--preset", Preset::NAME),
+        ("--template", PartOfSpeech::NAME),
+        ("--slot-wordlists", "slot wordlist"),
+    ] {
+        let output = run(&[option, "bogus"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stdout(&output).is_empty());
+        let err = stderr(&output);
+        assert!(
+            err.contains(&format!("`bogus` is not a valid {parent}")),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn test_enums_are_case_insensitive() {
+    for args in [
+        ["-W", "UPPER"],
+        ["-W", "Alternating-Lower-Upper"],
+        ["-T", "FIXED-BOTH"],
+        ["-T", "Adaptive"],
+        ["-r", "CSPRNG"],
+    ] {
+        let output = run(&args);
+        assert!(output.status.success(), "{args:?}: {}", stderr(&output));
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_output() {
+    let output = run_seeded(1, &["-c", "2"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "startling;SHAFT;cactus;SHACK;15+\nbullwhip@CHUNK@uniquely@FOOTBALL@03$\n"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_generate_is_the_default() {
+    let implicit = run_seeded(5, &["-c", "3", "-w", "2"]);
+    let explicit = run_seeded(5, &["generate", "-c", "3", "-w", "2"]);
+    let trailing = run_seeded(5, &["-c", "3", "generate", "-w", "2"]);
+    assert!(implicit.status.success());
+    assert_eq!(stdout(&implicit), stdout(&explicit));
+    assert_eq!(stdout(&implicit), stdout(&trailing));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_case_insensitive_enums_match() {
+    let lower = run_seeded(7, &["-W", "capitalize-first", "-T", "fixed-both"]);
+    let upper = run_seeded(7, &["-W", "CAPITALIZE-FIRST", "-T", "FIXED-BOTH"]);
+    assert!(lower.status.success());
+    assert_eq!(stdout(&lower), stdout(&upper));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_options() {
+    let output = run_seeded(
+        3,
+        &[
+            "-w",
+            "2",
+            "-W",
+            "upper",
+            "-b",
+            "1",
+            "-a",
+            "0",
+            "-T",
+            "fixed-front",
+            "-l",
+            "3",
+            "-p",
+            "#",
+            "-s",
+            "-",
+        ],
+    );
+    assert!(output.status.success());
+    let out = stdout(&output);
+    let password = out.trim_end_matches('\n');
+    assert!(password.starts_with("###"), "{password}");
+    let parts = password[3..].split('-').collect::<Vec<&str>>();
+    assert_eq!(parts.len(), 3, "{password}");
+    assert!(parts[0].len() == 1 && parts[0].chars().all(|c| c.is_ascii_digit()));
+    assert!(
+        parts[1..]
+            .iter()
+            .all(|p| p.chars().all(|c| c.is_ascii_uppercase()))
+    );
+}
+
+#[test]
+fn test_completions() {
+    for (shell, marker) in [
+        ("bash", "complete -F"),
+        ("zsh", "#compdef fmn-passgen"),
+        ("fish", "complete -c fmn-passgen"),
+    ] {
+        let output = run(&["completions", shell]);
+        assert!(output.status.success());
+        let out = stdout(&output);
+        assert!(out.contains(marker), "{shell}: {out}");
+        assert!(out.contains("word-transformation"));
+        assert!(out.contains("alternating-lower-upper"));
+    }
+}
+
+#[test]
+fn test_completions_invalid_shell() {
+    let output = run(&["completions", "powershell"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("`powershell` is not a valid Shell"));
+}
+
+#[test]
+fn test_man_page() {
+    let output = run(&["--man"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with(".TH"), "{out}");
+    assert!(out.contains(r"\-\-padding\-type"));
+}
+
+#[test]
+fn test_entropy() {
+    let output = run(&["entropy", "-w", "5", "-a", "0"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("5 from 7776 words"), "{out}");
+    assert!(out.contains("total"), "{out}");
+    assert!(!run(&["entropy", "-w", "99"]).status.success());
+}
+
+#[test]
+fn test_entropy_avoid_ambiguous() {
+    let output = run(&["entropy", "--avoid-ambiguous"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("avoiding ambiguous characters costs"), "{out}");
+    assert!(!stdout(&run(&["entropy"])).contains("ambiguous"));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_avoid_ambiguous() {
+    let output = run_seeded(
+        7,
+        &[
+            "-c",
+            "20",
+            "-W",
+            "upper",
+            "-C",
+            "alphanumeric",
+            "--avoid-ambiguous",
+        ],
+    );
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(!out.contains(['0', 'O', '1', 'l', 'I', '|', '`']), "{out}");
+}
+
+#[test]
+fn test_wordlist_stats() {
+    let output = run(&["wordlist", "-m", "4", "-M", "4"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("words: 7776"), "{out}");
+    assert!(out.contains("words of length 4 to 4: 467"), "{out}");
+    assert!(out.contains("prefix free: yes"), "{out}");
+}
+
+#[test]
+fn test_wordlist_check() {
+    let dir = env::temp_dir();
+    let good = dir.join(format!("fmn-passgen-good-{}.txt", process::id()));
+    let bad = dir.join(format!("fmn-passgen-bad-{}.txt", process::id()));
+    fs::write(&good, "correct\nhorse\nbattery\nstaple\n").expect("temp dir is writable");
+    fs::write(&bad, "correct\nhorse\nCorrect\n").expect("temp dir is writable");
+
+    let output = run(&["wordlist", good.to_str().expect("UTF-8 path")]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("words: 4"));
+
+    let output = run(&["wordlist", bad.to_str().expect("UTF-8 path")]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`Correct` appears more than once"));
+
+    let output = run(&["wordlist", "check", bad.to_str().expect("UTF-8 path")]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("unique prefix length: none"));
+
+    let output = run(&["wordlist", "check"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("words: 7776"));
+
+    let output = run(&["wordlist", "/nonexistent/wordlist.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let _ = fs::remove_file(good);
+    let _ = fs::remove_file(bad);
+}
+
+#[test]
+fn test_joined_words() {
+    // the default wordlist is prefix free, so running its words together loses nothing
+    let output = run(&["entropy", "-s", "", "-W", "lower"]);
+    assert!(output.status.success());
+    assert!(stderr(&output).is_empty(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("joined"));
+
+    let output = run(&["config", "-s", "", "--joined-words", "prefix-free"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("joined_words: prefix-free"));
+}
+
+#[test]
+fn test_template() {
+    let output = run(&["entropy", "--template", "adj noun verb adv"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("4 from 651, 333, 229 and 168 words"), "{out}");
+
+    let output = run(&["config", "-w", "5", "--template", "Verb noun"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("word_count: 2"), "{out}");
+    assert!(out.contains("template: \"verb noun\""), "{out}");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_template() {
+    let output = run_seeded(
+        1,
+        &[
+            "--template",
+            "verb noun",
+            "-W",
+            "lower",
+            "-s",
+            "-",
+            "-a",
+            "0",
+            "-T",
+            "none",
+        ],
+    );
+    assert!(output.status.success());
+    let out = stdout(&output);
+    let words = out.trim_end_matches('\n').split('-').collect::<Vec<&str>>();
+    assert_eq!(words.len(), 2, "{out}");
+    for (word, part) in words.iter().zip([PartOfSpeech::Verb, PartOfSpeech::Noun]) {
+        assert!(
+            part_of_speech_wordlist(part).iter().any(|w| &**w == *word),
+            "{word} is not a {}",
+            part.to_static_str()
+        );
+    }
+}
+
+#[test]
+fn test_slot_wordlists() {
+    let path = env::temp_dir().join(format!("fmn-passgen-custom-{}.txt", process::id()));
+    fs::write(&path, "kafka\nredis\nnginx\nhelm\n").expect("temp dir is writable");
+    let path = path.to_str().expect("UTF-8 path");
+
+    let slots = "custom eff-large eff-large";
+    let output = run(&[
+        "-W",
+        "lower",
+        "-s",
+        "-",
+        "--custom-wordlist",
+        path,
+        "--slot-wordlists",
+        slots,
+    ]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    let first = out.split('-').next().unwrap_or_default();
+    assert!(
+        ["kafka", "redis", "nginx", "helm"].contains(&first),
+        "{out}"
+    );
+
+    let output = run(&[
+        "entropy",
+        "--custom-wordlist",
+        path,
+        "--slot-wordlists",
+        slots,
+    ]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("3 from 4, 7776 and 7776 words"), "{out}");
+
+    for (mix, pool) in [
+        ("custom+eff-large", "1 from 7780 words"),
+        // each custom word is one of 12 + 7776 equally likely choices, three times over
+        ("custom:3+eff-large", "1 from 2596 words"),
+    ] {
+        let output = run(&[
+            "entropy",
+            "--custom-wordlist",
+            path,
+            "--slot-wordlists",
+            mix,
+        ]);
+        assert!(output.status.success());
+        let out = stdout(&output);
+        assert!(out.contains(pool), "{mix}: {out}");
+    }
+
+    let output = run(&[
+        "config",
+        "export",
+        "--custom-wordlist",
+        path,
+        "--slot-wordlists",
+        slots,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("can not be exported"));
+    fs::remove_file(path).expect("temp file is removable");
+
+    let output = run(&["--slot-wordlists", "custom"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`custom` is not a valid slot wordlist"));
+}
+
+#[test]
+fn test_exclude_words() {
+    let path = env::temp_dir().join(format!("fmn-passgen-exclude-{}.txt", process::id()));
+    fs::write(&path, "abacus\nAbdomen\n").expect("temp dir is writable");
+    let path = path.to_str().expect("UTF-8 path");
+
+    let output = run(&["entropy", "--exclude-words", path]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("4 from 7774 words"));
+
+    let output = run(&["config", "--blocklist", "--exclude-words", path]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("blocklist: true"), "{out}");
+    assert!(out.contains("excluded_words: \"abacus abdomen\""), "{out}");
+
+    let output = run(&["config", "export", "--exclude-words", path]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("can not be exported"));
+
+    let output = run(&["--exclude-words", "/nonexistent/words.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("/nonexistent/words.txt"));
+
+    let _ = fs::remove_file(path);
+}
+
+#[test]
+fn test_config_print() {
+    let output = run(&["config", "-W", "UPPER"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("word_transformation: upper\n"));
+    assert_eq!(
+        stdout(&output),
+        stdout(&run(&["config", "print", "-W", "upper"]))
+    );
+}
+
+#[test]
+fn test_config_check() {
+    let output = run(&["config", "check", "-w", "3"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).is_empty());
+    let output = run(&["config", "check", "-w", "33"]);
+    assert_eq!(output.status.code(), Some(1));
+    let output = run(&["config", "bogus"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("`bogus` is not a valid ConfigAction"));
+}
+
+/// The exported command line, run by a shell, makes passwords with the same config.
+#[cfg(unix)]
+#[test]
+fn test_config_export_round_trip() {
+    let options = [
+        "--template",
+        "adj noun verb",
+        "--word-transformation",
+        "capitalize-first-word",
+        "--padding-characters",
+        "'\"$ ",
+        "--padding-fill",
+        "random",
+        "--separator-type",
+        "sequence",
+        "--separator-sequence",
+        "-.'",
+        "--avoid-ambiguous",
+        "--easy-typing",
+        "--keyboard",
+        "azerty",
+        "--max-length",
+        "40",
+    ];
+    let output = run(&iter::once("config")
+        .chain(options)
+        .chain(["export"])
+        .collect::<Vec<&str>>());
+    assert!(output.status.success(), "{}", stderr(&output));
+    let exported = stdout(&output);
+    let command = exported
+        .trim_end()
+        .strip_prefix("fmn-passgen generate")
+        .expect("the export is a generate command");
+    let reparsed = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("\"$0\" config print{command}"))
+        .arg(env!("CARGO_BIN_EXE_fmn-passgen"))
+        .env_remove(TEST_SEED_VAR)
+        .output()
+        .expect("sh should run");
+    assert!(reparsed.status.success(), "{}", stderr(&reparsed));
+    let original = run(&iter::once("config").chain(options).collect::<Vec<&str>>());
+    assert_eq!(stdout(&reparsed), stdout(&original));
+}
+
+#[test]
+fn test_explain() {
+    for args in [
+        &["--explain"][..],
+        &["generate", "--explain"],
+        &["entropy", "--explain"],
+    ] {
+        let output = run(args);
+        assert!(output.status.success());
+        let out = stdout(&output);
+        assert!(out.starts_with("component"), "{out}");
+        assert!(
+            out.contains("word pool: 7776 words of length 3 to 11"),
+            "{out}"
+        );
+        assert!(out.contains("average time to guess"), "{out}");
+    }
+    let out = stdout(&run(&["--explain", "-m", "5", "-M", "5"]));
+    assert!(
+        out.contains("word pool: 928 words of length 5 to 5"),
+        "{out}"
+    );
+    // 4 words of 5 with their 2 digits, 4 separators and 1 padding fit exactly
+    let out = stdout(&run(&[
+        "--explain",
+        "-m",
+        "5",
+        "-M",
+        "5",
+        "--max-length",
+        "27",
+    ]));
+    assert!(
+        out.contains("max length 27 leaves 39.43 of the 39.43 bits of word choices"),
+        "{out}"
+    );
+    let out = stdout(&run(&["--explain", "--max-length", "24"]));
+    assert!(
+        out.contains("max length 24 leaves 40.59 of the 51.70 bits of word choices"),
+        "{out}"
+    );
+}
+
+#[test]
+fn test_policy_enforced() {
+    let output = run(&[
+        "-c",
+        "20",
+        "-w",
+        "3",
+        "--max-length",
+        "24",
+        "--min-digits",
+        "3",
+        "-b",
+        "1",
+        "--max-repeat",
+        "1",
+    ]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).is_empty());
+    let out = stdout(&output);
+    assert_eq!(out.lines().count(), 20);
+    for password in out.lines() {
+        assert!(password.chars().count() <= 24, "{password}");
+        assert!(password.chars().filter(char::is_ascii_digit).count() >= 3);
+        let chars = password.chars().collect::<Vec<char>>();
+        assert!(chars.windows(2).all(|w| w[0] != w[1]), "{password}");
+    }
+}
+
+#[test]
+fn test_policy_conflict() {
+    let output = run(&["-W", "lower", "--min-upper", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty());
+    let err = stderr(&output);
+    assert!(
+        err.contains("warning: uppercase characters are required"),
+        "{err}"
+    );
+    assert!(
+        err.contains("no password satisfying the policy was found"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_typing() {
+    let output = run(&["typing", "-c", "2", "--keyboard", "mobile"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert_eq!(out.lines().count(), 2);
+    for line in out.lines() {
+        let (password, cost) = line.split_once('\t').unwrap();
+        assert!(!password.is_empty());
+        assert!(cost.contains(" keystrokes, "), "{cost}");
+        assert!(cost.contains(" layer switches, "), "{cost}");
+    }
+}
+
+#[test]
+fn test_easy_typing() {
+    let output = run(&["config", "--easy-typing", "--keyboard", "qwertz"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("keyboard: qwertz"), "{out}");
+    assert!(out.contains("easy_typing: true"), "{out}");
+    assert!(out.contains("separator_characters: \"^-+.\""), "{out}");
+    let output = run(&["entropy", "--easy-typing"]);
+    assert!(stdout(&output).contains("7776 words"));
+}
+
+#[test]
+fn test_preset() {
+    let output = run(&["entropy", "--preset", "mobile"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(
+        out.contains("the mobile preset costs 1.70 bits against the desktop default"),
+        "{out}"
+    );
+    assert!(!stdout(&run(&["entropy"])).contains("preset"));
+    let output = run(&["config", "--preset", "mobile", "-W", "lower"]);
+    let out = stdout(&output);
+    assert!(out.contains("preset: mobile"), "{out}");
+    assert!(out.contains("word_transformation: lower"), "{out}");
+    assert!(out.contains("keyboard: mobile"), "{out}");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_seeded_preset() {
+    let output = run_seeded(7, &["typing", "--preset", "mobile"]);
+    let out = stdout(&output);
+    let (password, cost) = out.trim_end().split_once('\t').unwrap();
+    assert_eq!(
+        password.chars().filter(char::is_ascii_uppercase).count(),
+        1,
+        "{password}"
+    );
+    assert!(cost.contains(" 1 shift presses, "), "{cost}");
+}
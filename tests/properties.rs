@@ -0,0 +1,197 @@
+//! Property based tests for [`ConfigBuilder`] and [`PasswordMaker`].
+//!
+//! Building a config from any input must never panic,
+//! and every password must have exactly the structure its [`Config`] describes.
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use fmn_passgen::config::Config;
+use fmn_passgen::config::ConfigBuilder;
+use fmn_passgen::password_maker::PasswordMaker;
+use fmn_passgen::types::PaddingType;
+use fmn_passgen::types::StrEnum;
+use fmn_passgen::types::WordTransformationType;
+
+/// Purely alphabetic words from 1 to 12 characters long,
+/// so that words can be told apart from digits, separators and padding.
+fn make_wordlist() -> Vec<String> {
+    (1..=12)
+        .flat_map(|len| {
+            ["abcdefghijkl", "mnopqrstuvwx"]
+                .into_iter()
+                .map(move |letters| letters[..len].to_owned())
+        })
+        .collect()
+}
+
+fn symbols() -> impl Strategy<Value = String> {
+    "[!@$%^&*_+=:|~?/.;-]{0,6}"
+}
+
+/// Any [`StrEnum`] member name, in any case, as it might be typed on the command line.
+fn member<T: StrEnum>() -> impl Strategy<Value = String> {
+    let names = T::NAME_MEMBER_ARR
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<&str>>();
+    (prop::sample::select(names), any::<bool>()).prop_map(|(name, upper)| {
+        if upper {
+            name.to_ascii_uppercase()
+        } else {
+            name.to_owned()
+        }
+    })
+}
+
+prop_compose! {
+    /// A valid [`Config`] whose separators and padding are never alphanumeric.
+    fn valid_config()(
+        word_count in 0..=8_u8,
+        word_min_length in 1..=12_u8,
+        word_max_length_offset in 0..=12_u8,
+        word_transformation in member::<WordTransformationType>(),
+        digits_before in 0..=6_u8,
+        digits_after in 0..=6_u8,
+        padding_type in member::<PaddingType>(),
+        padding_length in prop::option::of(0..=64_u8),
+        padding_characters in symbols(),
+        separator_characters in symbols(),
+    ) -> Config {
+        ConfigBuilder::new()
+            .word_count(Some(word_count.to_string()))
+            .word_min_length(Some(word_min_length.to_string()))
+            .word_max_length(Some(word_min_length.saturating_add(word_max_length_offset).to_string()))
+            .word_transformation(Some(word_transformation))
+            .digits_before(Some(digits_before.to_string()))
+            .digits_after(Some(digits_after.to_string()))
+            .padding_type(Some(padding_type))
+            .padding_length(padding_length.map(|n| n.to_string()))
+            .padding_characters(Some(padding_characters))
+            .separator_characters(Some(separator_characters))
+            .build()
+            .expect("every generated config is valid")
+    }
+}
+
+/// Check whether `chars` can be split into exactly `n` pieces which all satisfy `is_word`.
+fn can_segment(chars: &[char], n: usize, is_word: &impl Fn(&[char]) -> bool) -> bool {
+    if n == 0 {
+        return chars.is_empty();
+    }
+    (1..=chars.len()).any(|i| is_word(&chars[..i]) && can_segment(&chars[i..], n - 1, is_word))
+}
+
+/// Check that `password` has the structure described by `config`.
+fn assert_structure(config: &Config, wordlist: &[String], password: &str) {
+    let chars = password.chars().collect::<Vec<char>>();
+    let is_part = |c: &char| c.is_ascii_alphanumeric();
+    // padding and separators are symbols, so the unpadded password starts and ends with a part
+    let first_part = chars.iter().position(is_part);
+    let last_part = chars.iter().rposition(is_part);
+    let (front, unpadded, back) = match (first_part, last_part) {
+        (Some(first), Some(last)) => (&chars[..first], &chars[first..=last], &chars[last + 1..]),
+        _ => (&chars[..], &chars[..0], &chars[..0]),
+    };
+
+    let word_len = config.word_min_length as usize..=config.word_max_length as usize;
+    let expected_word_count = if wordlist.iter().any(|w| word_len.contains(&w.len())) {
+        config.word_count as usize
+    } else {
+        0
+    };
+    let is_word = |part: &[char]| {
+        let part = part.iter().collect::<String>();
+        word_len.contains(&part.len()) && wordlist.contains(&part.to_ascii_lowercase())
+    };
+    let is_digits = |part: &[char], len: u8| {
+        part.len() == len as usize && part.iter().all(char::is_ascii_digit)
+    };
+
+    if config.separator_characters.is_empty() {
+        // digits and words run together
+        let (before, rest) = unpadded.split_at(config.digits_before as usize);
+        let (words, after) = rest.split_at(rest.len() - config.digits_after as usize);
+        assert!(is_digits(before, config.digits_before));
+        assert!(is_digits(after, config.digits_after));
+        assert!(can_segment(words, expected_word_count, &is_word));
+    } else {
+        // a single separator, used between every part
+        let mut parts = match unpadded.iter().find(|c| !is_part(c)) {
+            _ if unpadded.is_empty() => Vec::new(),
+            None => vec![unpadded],
+            Some(separator) => {
+                assert!(config.separator_characters.contains(separator));
+                unpadded.split(|c| c == separator).collect::<Vec<&[char]>>()
+            }
+        };
+        if config.digits_before > 0 {
+            assert!(is_digits(parts.remove(0), config.digits_before));
+        }
+        if config.digits_after > 0 {
+            assert!(is_digits(
+                parts.pop().expect("digits after"),
+                config.digits_after
+            ));
+        }
+        assert_eq!(parts.len(), expected_word_count);
+        assert!(parts.into_iter().all(is_word));
+    }
+
+    // a single padding character, repeated
+    let len = config.padding_length as usize;
+    let adaptive = len.saturating_sub(unpadded.len());
+    let (front_len, back_len) = match config.padding_type {
+        PaddingType::None => (0, 0),
+        PaddingType::FixedFront => (len, 0),
+        PaddingType::FixedBack => (0, len),
+        PaddingType::FixedBoth | PaddingType::Fixed => (len, len),
+        PaddingType::AdaptiveFront => (adaptive, 0),
+        PaddingType::AdaptiveBack | PaddingType::Adaptive => (0, adaptive),
+    };
+    if unpadded.is_empty() {
+        assert_eq!(front.len(), front_len + back_len);
+    } else {
+        assert_eq!(front.len(), front_len);
+        assert_eq!(back.len(), back_len);
+    }
+    let padding = front.iter().chain(back.iter()).collect::<Vec<&char>>();
+    assert!(padding.iter().all(|c| *c == padding[0]));
+    assert!(
+        padding
+            .iter()
+            .all(|c| config.padding_characters.contains(c))
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_build_never_panics(
+        values in prop::collection::vec(prop::option::of(".*"), ConfigBuilder::FIELD_NAMES.len()),
+        seed in any::<u64>(),
+    ) {
+        let mut builder = ConfigBuilder::new();
+        for (name, value) in ConfigBuilder::FIELD_NAMES.iter().zip(values) {
+            prop_assert!(builder.set(name, value));
+        }
+        if let Ok(config) = builder.build() {
+            prop_assert!(config.word_min_length <= config.word_max_length);
+            let mut maker = PasswordMaker::with_rng(SmallRng::seed_from_u64(seed), config);
+            let _ = maker.make_password();
+        }
+    }
+
+    #[test]
+    fn test_password_structure(config in valid_config(), seed in any::<u64>()) {
+        let wordlist = make_wordlist();
+        let mut maker = PasswordMaker::with_rng(SmallRng::seed_from_u64(seed), config.clone());
+        maker.wordlist = wordlist.iter().map(|w| Box::from(w.as_str())).collect();
+        for _ in 0..4 {
+            assert_structure(
+                &config,
+                &wordlist,
+                &maker.make_password().expect("there is no policy to break"),
+            );
+        }
+    }
+}
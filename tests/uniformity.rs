@@ -0,0 +1,180 @@
+//! Statistical checks that every random choice made by [`PasswordMaker`] is uniform.
+//!
+//! Each test isolates one component of the password with a [`Config`] in which everything else
+//! is switched off, generates many passwords from seeded RNGs, and runs Pearson's chi-squared
+//! goodness of fit test against a uniform distribution.
+//! Seeded RNGs make the outcome deterministic, so the significance level guards against
+//! genuinely biased sampling rather than bad luck.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::RngCore;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+use fmn_passgen::config::Config;
+use fmn_passgen::config::ConfigBuilder;
+use fmn_passgen::password_maker::PasswordMaker;
+use fmn_passgen::types::PaddingType;
+use fmn_passgen::types::WordTransformationType;
+
+const SEEDS: [u64; 3] = [1, 42, 13_414_357_264_162_109_690];
+const SAMPLES_PER_SEED: usize = 20_000;
+/// z-score for a one sided significance level of 0.001
+const Z_0_001: f64 = 3.090_232;
+
+/// A deliberately awkward number of words, so that modulo bias would show if present.
+const WORDLIST: [&str; 7] = ["a", "b", "c", "d", "e", "f", "g"];
+
+/// A config which produces empty passwords, to be selectively turned back on.
+fn make_silent_config() -> Config {
+    ConfigBuilder::new()
+        .word_count(Some("0".to_owned()))
+        .word_min_length(Some("1".to_owned()))
+        .word_transformation(Some("none".to_owned()))
+        .digits_after(Some("0".to_owned()))
+        .padding_type(Some("none".to_owned()))
+        .separator_characters(Some(String::new()))
+        .build()
+        .expect("a config with everything switched off is valid")
+}
+
+fn make_maker(seed: u64, config: Config) -> PasswordMaker<SmallRng> {
+    let mut maker = PasswordMaker::with_rng(SmallRng::seed_from_u64(seed), config);
+    maker.wordlist = WORDLIST.into_iter().map(Box::from).collect();
+    maker
+}
+
+/// Upper critical value of the chi-squared distribution with `df` degrees of freedom
+/// at a significance level of 0.001, using the Wilson-Hilferty approximation.
+fn chi_squared_critical(df: usize) -> f64 {
+    #[expect(clippy::cast_precision_loss, reason = "df is small")]
+    let k = df as f64;
+    let h = 2.0 / (9.0 * k);
+    k * Z_0_001.mul_add(h.sqrt(), 1.0 - h).powi(3)
+}
+
+/// Pearson's chi-squared statistic for `counts` against a uniform distribution over `categories`.
+///
+/// Categories that never occurred still count towards the expected distribution.
+fn chi_squared<K: Hash + Eq>(counts: &HashMap<K, usize>, categories: &[K]) -> f64 {
+    let total: usize = counts.values().sum();
+    #[expect(clippy::cast_precision_loss, reason = "sample sizes are small")]
+    let expected = total as f64 / categories.len() as f64;
+    categories
+        .iter()
+        .map(|k| {
+            #[expect(clippy::cast_precision_loss, reason = "sample sizes are small")]
+            let observed = counts.get(k).copied().unwrap_or(0) as f64;
+            (observed - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+/// Assert that `counts` is consistent with a uniform distribution over `categories`.
+fn assert_uniform<K: Hash + Eq + std::fmt::Debug>(counts: &HashMap<K, usize>, categories: &[K]) {
+    assert!(
+        counts.keys().all(|k| categories.contains(k)),
+        "unexpected category in {counts:?}"
+    );
+    let statistic = chi_squared(counts, categories);
+    let critical = chi_squared_critical(categories.len() - 1);
+    assert!(
+        statistic < critical,
+        "chi-squared statistic {statistic} exceeds {critical} for {counts:?}"
+    );
+}
+
+/// Generate passwords for every seed and tally `f(password)`.
+fn tally<K: Hash + Eq>(config: &Config, f: impl Fn(&str) -> K) -> HashMap<K, usize> {
+    let mut counts = HashMap::new();
+    for seed in SEEDS {
+        let mut maker = make_maker(seed, config.clone());
+        for _ in 0..SAMPLES_PER_SEED {
+            let password = maker.make_password().expect("there is no policy to break");
+            *counts.entry(f(&password)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn test_words_uniform() {
+    let mut config = make_silent_config();
+    config.word_count = 1;
+    let counts = tally(&config, str::to_owned);
+    let categories = WORDLIST.map(str::to_owned);
+    assert_uniform(&counts, &categories);
+}
+
+/// Every word slot should be independently uniform, not just the first.
+#[test]
+fn test_word_pairs_uniform() {
+    let mut config = make_silent_config();
+    config.word_count = 2;
+    let counts = tally(&config, str::to_owned);
+    let categories = WORDLIST
+        .iter()
+        .flat_map(|a| WORDLIST.iter().map(move |b| format!("{a}{b}")))
+        .collect::<Vec<String>>();
+    assert_uniform(&counts, &categories);
+}
+
+#[test]
+fn test_digits_uniform() {
+    let mut config = make_silent_config();
+    config.digits_before = 1;
+    let counts = tally(&config, str::to_owned);
+    let categories = ('0'..='9').map(String::from).collect::<Vec<String>>();
+    assert_uniform(&counts, &categories);
+}
+
+#[test]
+fn test_separators_uniform() {
+    let mut config = make_silent_config();
+    config.word_count = 2;
+    config.separator_characters = Config::default().separator_characters;
+    // single character words, so the separator is always the second character
+    let counts = tally(&config, |p| p.chars().nth(1).unwrap());
+    assert_uniform(&counts, &config.separator_characters);
+}
+
+#[test]
+fn test_padding_characters_uniform() {
+    let mut config = make_silent_config();
+    config.padding_type = PaddingType::FixedBack;
+    config.padding_length = 1;
+    config.padding_characters = Config::default().padding_characters;
+    let counts = tally(&config, |p| p.chars().next().unwrap());
+    assert_uniform(&counts, &config.padding_characters);
+}
+
+#[test]
+fn test_random_upper_lower_uniform() {
+    let mut config = make_silent_config();
+    config.word_count = 3;
+    config.word_transformation = WordTransformationType::RandomUpperLower;
+    let counts = tally(&config, |p| {
+        p.chars()
+            .map(|c| c.is_ascii_uppercase())
+            .collect::<Vec<bool>>()
+    });
+    let categories = (0..8_u8)
+        .map(|i| (0..3).map(|bit| i & (1 << bit) != 0).collect())
+        .collect::<Vec<Vec<bool>>>();
+    assert_uniform(&counts, &categories);
+}
+
+/// Make sure the checks above are sensitive enough to catch the classic modulo bias mistake.
+#[test]
+fn test_modulo_bias_is_detected() {
+    let mut rng = SmallRng::seed_from_u64(SEEDS[0]);
+    let mut counts = HashMap::new();
+    for _ in 0..SAMPLES_PER_SEED * SEEDS.len() {
+        #[expect(clippy::cast_possible_truncation, reason = "truncation is the point")]
+        let byte = rng.next_u32() as u8;
+        *counts.entry(byte % 100).or_insert(0) += 1;
+    }
+    let categories = (0..100).collect::<Vec<u8>>();
+    assert!(chi_squared(&counts, &categories) > chi_squared_critical(categories.len() - 1));
+}